@@ -61,28 +61,28 @@ impl Committee {
     pub fn set_online(&self, member_id: &IssuerID, online: bool) -> Self {
         let mut new_committee = Committee(self.0.clone());
 
-        if let Some(member) = self.0.1.members_by_id.get(member_id) {
-            if member.is_online() != online {
-                // Create a mutable clone of the CommitteeData if necessary
-                let data = Arc::make_mut(&mut new_committee.0);
-
-                // Update online weight
-                if online {
-                    data.1.online_weight += member.weight();
-                } else {
-                    data.1.online_weight -= member.weight();
-                }
-
-                // Make the HashMap mutable, then make the member mutable, and update its state
-                Arc::make_mut(
-                    Arc::make_mut(&mut data.1.members_by_id)
-                        .get_mut(member_id)
-                        .expect("member must exist"),
-                )
-                .set_online(online);
-
-                data.0 = Id::new(&data.1)
+        if let Some(member) = self.0.1.members_by_id.get(member_id)
+            && member.is_online() != online
+        {
+            // Create a mutable clone of the CommitteeData if necessary
+            let data = Arc::make_mut(&mut new_committee.0);
+
+            // Update online weight
+            if online {
+                data.1.online_weight += member.weight();
+            } else {
+                data.1.online_weight -= member.weight();
             }
+
+            // Make the HashMap mutable, then make the member mutable, and update its state
+            Arc::make_mut(
+                Arc::make_mut(&mut data.1.members_by_id)
+                    .get_mut(member_id)
+                    .expect("member must exist"),
+            )
+            .set_online(online);
+
+            data.0 = Id::new(&data.1)
         }
 
         new_committee
@@ -146,3 +146,32 @@ impl Clone for Committee {
         Self(self.0.clone())
     }
 }
+
+mod serialization {
+    use std::sync::Arc;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Committee;
+    use crate::{bft::Members, ids::Id};
+
+    impl Serialize for Committee {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            // the commitment is derived from the members, so only the members are serialized
+            self.0.1.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Committee {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let members = Members::deserialize(deserializer)?;
+            Ok(Committee(Arc::new((Id::new(&members), members))))
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::{backtrace::Backtrace, fmt, sync::Arc};
+
+use crate::{
+    blocks::Block,
+    errors::{Error, Result},
+    ids::IssuerID,
+};
+
+/// Evidence that a committee member issued two conflicting milestones.
+///
+/// The evidence holds both signed blocks, so that every node can verify that they were issued by
+/// the accused member. Whether both blocks are milestones of the same round is decided by the
+/// virtual voting of each node once it processed them, so nodes that receive the evidence process
+/// its blocks instead of trusting the sender.
+#[derive(Clone)]
+pub struct Equivocation {
+    issuer_id: IssuerID,
+    blocks: Arc<[Block; 2]>,
+}
+
+impl Equivocation {
+    /// Creates the evidence of two blocks of the same issuer. The blocks are ordered by their id,
+    /// so that all nodes build the same evidence regardless of the order they received them in.
+    pub fn new(first: Block, second: Block) -> Result<Self> {
+        let Block::NetworkBlock(_, network_block) = &first else {
+            return Err(Self::invalid("genesis blocks have no issuer"));
+        };
+
+        let issuer_id = network_block.issuer_id.clone();
+        let blocks = Arc::new(match first.id() <= second.id() {
+            true => [first, second],
+            false => [second, first],
+        });
+
+        let equivocation = Self { issuer_id, blocks };
+        equivocation.verify()?;
+
+        Ok(equivocation)
+    }
+
+    pub fn issuer_id(&self) -> &IssuerID {
+        &self.issuer_id
+    }
+
+    pub fn blocks(&self) -> &[Block; 2] {
+        &self.blocks
+    }
+
+    /// Checks that the evidence holds two distinct blocks that were both signed by its issuer.
+    pub fn verify(&self) -> Result<()> {
+        if self.blocks[0].id() == self.blocks[1].id() {
+            return Err(Self::invalid("blocks must be distinct"));
+        }
+
+        for block in self.blocks.iter() {
+            let Block::NetworkBlock(_, network_block) = block else {
+                return Err(Self::invalid("genesis blocks have no issuer"));
+            };
+            if network_block.issuer_id != self.issuer_id {
+                return Err(Self::invalid("blocks must have the same issuer"));
+            }
+            block.verify_signature()?;
+        }
+
+        Ok(())
+    }
+
+    fn invalid(reason: &'static str) -> Error {
+        Error::InvalidEvidence {
+            reason,
+            backtrace: Backtrace::capture(),
+        }
+    }
+}
+
+impl fmt::Debug for Equivocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Equivocation({}, {:?}, {:?})",
+            self.issuer_id,
+            self.blocks[0].id(),
+            self.blocks[1].id()
+        )
+    }
+}
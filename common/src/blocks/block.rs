@@ -1,6 +1,12 @@
-use std::{fmt, fmt::Debug};
+use std::{backtrace::Backtrace, fmt, fmt::Debug};
 
-use crate::{blocks::NetworkBlock, ids::BlockID};
+use crate::{
+    blocks::NetworkBlock,
+    codec,
+    crypto::PublicKey,
+    errors::{Error, Result},
+    ids::BlockID,
+};
 
 #[derive(Clone)]
 pub enum Block {
@@ -9,6 +15,8 @@ pub enum Block {
 }
 
 impl Block {
+    pub const VERSION: u8 = 1;
+
     pub fn id(&self) -> &BlockID {
         match &self {
             Block::GenesisBlock(id) => id,
@@ -22,6 +30,58 @@ impl Block {
             Block::NetworkBlock(_, network_block) => network_block.parents.as_slice(),
         }
     }
+
+    pub fn payload(&self) -> &[u8] {
+        match &self {
+            Block::GenesisBlock(_) => &[],
+            Block::NetworkBlock(_, network_block) => network_block.payload.as_slice(),
+        }
+    }
+
+    pub fn verify_signature(&self) -> Result<()> {
+        match &self {
+            Block::GenesisBlock(_) => Ok(()),
+            Block::NetworkBlock(id, network_block) => {
+                let public_key = PublicKey::try_from(&network_block.issuer_id)?;
+                match public_key.verify(id.as_slice(), &network_block.signature) {
+                    true => Ok(()),
+                    false => Err(Error::InvalidSignature {
+                        block_id: id.clone(),
+                        issuer_id: network_block.issuer_id.clone(),
+                        backtrace: Backtrace::capture(),
+                    }),
+                }
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        codec::encode(Self::VERSION, &serialization::WireBlock::from(self))
+    }
+
+    /// Decodes a block that was received from the wire. Genesis blocks are only ever created
+    /// locally, so they are rejected (their signature is not checked, which would allow any peer
+    /// to inject one).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match codec::decode(Self::VERSION, bytes)? {
+            serialization::WireBlock::GenesisBlock(id) => Err(Error::UnexpectedGenesisBlock {
+                block_id: id,
+                backtrace: Backtrace::capture(),
+            }),
+            serialization::WireBlock::NetworkBlock(id, network_block) => {
+                let actual = BlockID::new(&network_block);
+                if actual != id {
+                    return Err(Error::BlockIDMismatch {
+                        expected: id,
+                        actual,
+                        backtrace: Backtrace::capture(),
+                    });
+                }
+
+                Ok(Block::NetworkBlock(id, network_block))
+            }
+        }
+    }
 }
 
 impl From<NetworkBlock> for Block {
@@ -39,4 +99,30 @@ impl Debug for Block {
     }
 }
 
-mod network_block {}
+mod serialization {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        blocks::{Block, NetworkBlock},
+        ids::BlockID,
+    };
+
+    /// Wire representation of a [`Block`] that carries the claimed ID next to the content so that
+    /// decoders can verify it.
+    #[derive(Deserialize, Serialize)]
+    pub(super) enum WireBlock {
+        GenesisBlock(BlockID),
+        NetworkBlock(BlockID, NetworkBlock),
+    }
+
+    impl From<&Block> for WireBlock {
+        fn from(block: &Block) -> Self {
+            match block {
+                Block::GenesisBlock(id) => WireBlock::GenesisBlock(id.clone()),
+                Block::NetworkBlock(id, network_block) => {
+                    WireBlock::NetworkBlock(id.clone(), network_block.clone())
+                }
+            }
+        }
+    }
+}
@@ -1,20 +1,64 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
+    codec,
+    crypto::{Signature, SigningKey},
+    errors::Result,
     hash::{Hashable, Hasher},
     ids::{BlockID, IssuerID},
 };
 
-#[derive(Clone)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct NetworkBlock {
     pub parents: Vec<BlockID>,
     pub issuer_id: IssuerID,
+    pub issuing_time: u64,
+    pub payload: Vec<u8>,
+    pub signature: Signature,
+}
+
+impl NetworkBlock {
+    pub const VERSION: u8 = 1;
+
+    /// Creates a block that is issued and signed by the owner of `signing_key`.
+    ///
+    /// The signature covers the [`BlockID`], which is the hash of the [`Hashable`] preimage.
+    pub fn new(
+        parents: Vec<BlockID>,
+        issuing_time: u64,
+        payload: Vec<u8>,
+        signing_key: &SigningKey,
+    ) -> Self {
+        let mut network_block = Self {
+            parents,
+            issuer_id: signing_key.issuer_id(),
+            issuing_time,
+            payload,
+            signature: Signature::default(),
+        };
+        network_block.signature = signing_key.sign(BlockID::new(&network_block).as_slice());
+
+        network_block
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        codec::encode(Self::VERSION, self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        codec::decode(Self::VERSION, bytes)
+    }
 }
 
 impl Hashable for NetworkBlock {
     fn hash<H: Hasher>(&self, hasher: &mut H) {
-        hasher.update(&self.parents.len().to_be_bytes());
+        hasher.update(&(self.parents.len() as u64).to_be_bytes());
         for parent in &self.parents {
             hasher.update(parent.as_slice());
         }
         hasher.update(self.issuer_id.as_slice());
+        hasher.update(&self.issuing_time.to_be_bytes());
+        hasher.update(&(self.payload.len() as u64).to_be_bytes());
+        hasher.update(&self.payload);
     }
 }
@@ -0,0 +1,41 @@
+use std::{backtrace::Backtrace, str::FromStr};
+
+use serde::{Deserialize, Deserializer, de::Error as _};
+
+use crate::errors::{Error, Result};
+
+/// Parses 32 bytes from 64 hex digits that are optionally prefixed with `0x`.
+pub(crate) fn parse_hex(s: &str) -> Result<[u8; 32]> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    // the input is not part of the error, as it might be a secret key
+    let invalid = |reason: &str| Error::DecodingFailed {
+        reason: reason.to_string(),
+        backtrace: Backtrace::capture(),
+    };
+    if digits.len() != 64 {
+        return Err(invalid("expected 64 hex digits"));
+    }
+
+    let mut bytes = [0; 32];
+    for (byte, pair) in bytes.iter_mut().zip(digits.as_bytes().chunks(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|_| invalid("invalid hex digit"))?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid("invalid hex digit"))?;
+    }
+
+    Ok(bytes)
+}
+
+/// Deserializes a value from a string of 64 hex digits (like ids and signing keys) for
+/// `#[serde(deserialize_with = "...")]`.
+pub fn deserialize_hex<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr<Err = Error>,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(|error| match error {
+            Error::DecodingFailed { reason, .. } => D::Error::custom(reason),
+            error => D::Error::custom(error),
+        })
+}
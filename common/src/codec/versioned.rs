@@ -0,0 +1,39 @@
+use std::backtrace::Backtrace;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::errors::{Error, Result};
+
+/// Encodes `value` as postcard bytes prefixed with a single `version` byte.
+pub fn encode<T: Serialize>(version: u8, value: &T) -> Vec<u8> {
+    postcard::to_allocvec(&(version, value)).expect("encoding into a vec must not fail")
+}
+
+/// Decodes bytes produced by [`encode`], rejecting unknown versions and trailing data.
+pub fn decode<T: DeserializeOwned>(version: u8, bytes: &[u8]) -> Result<T> {
+    let (found, remaining) = postcard::take_from_bytes::<u8>(bytes).map_err(decoding_failed)?;
+    if found != version {
+        return Err(Error::UnsupportedVersion {
+            expected: version,
+            found,
+            backtrace: Backtrace::capture(),
+        });
+    }
+
+    let (value, remaining) = postcard::take_from_bytes::<T>(remaining).map_err(decoding_failed)?;
+    if !remaining.is_empty() {
+        return Err(Error::TrailingBytes {
+            count: remaining.len(),
+            backtrace: Backtrace::capture(),
+        });
+    }
+
+    Ok(value)
+}
+
+fn decoding_failed(error: postcard::Error) -> Error {
+    Error::DecodingFailed {
+        reason: error.to_string(),
+        backtrace: Backtrace::capture(),
+    }
+}
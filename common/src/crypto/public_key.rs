@@ -0,0 +1,47 @@
+use std::backtrace::Backtrace;
+
+use crate::{
+    crypto::Signature,
+    errors::{Error, Result},
+    ids::IssuerID,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(ed25519_dalek::VerifyingKey);
+
+impl PublicKey {
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        self.0.verify_strict(message, signature).is_ok()
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+}
+
+impl From<ed25519_dalek::VerifyingKey> for PublicKey {
+    fn from(key: ed25519_dalek::VerifyingKey) -> Self {
+        Self(key)
+    }
+}
+
+impl TryFrom<&IssuerID> for PublicKey {
+    type Error = Error;
+
+    fn try_from(issuer_id: &IssuerID) -> Result<Self> {
+        ed25519_dalek::VerifyingKey::from_bytes(issuer_id)
+            .map(Self)
+            .map_err(|_| Error::InvalidPublicKey {
+                issuer_id: issuer_id.clone(),
+                backtrace: Backtrace::capture(),
+            })
+    }
+}
+
+impl From<&PublicKey> for IssuerID {
+    /// Issuer IDs are the raw bytes of the issuer's public key, so that every block can be
+    /// verified without a separate key registry.
+    fn from(public_key: &PublicKey) -> Self {
+        IssuerID::from(public_key.to_bytes())
+    }
+}
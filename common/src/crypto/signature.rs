@@ -0,0 +1,32 @@
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Signature(ed25519_dalek::Signature);
+
+impl From<ed25519_dalek::Signature> for Signature {
+    fn from(signature: ed25519_dalek::Signature) -> Self {
+        Self(signature)
+    }
+}
+
+impl From<[u8; 64]> for Signature {
+    fn from(bytes: [u8; 64]) -> Self {
+        Self(ed25519_dalek::Signature::from_bytes(&bytes))
+    }
+}
+
+impl Default for Signature {
+    fn default() -> Self {
+        Self::from([0; 64])
+    }
+}
+
+impl Deref for Signature {
+    type Target = ed25519_dalek::Signature;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -0,0 +1,42 @@
+use std::str::FromStr;
+
+use ed25519_dalek::Signer;
+
+use crate::{
+    codec::parse_hex,
+    crypto::{PublicKey, Signature},
+    errors::{Error, Result},
+    ids::IssuerID,
+};
+
+#[derive(Clone)]
+pub struct SigningKey(ed25519_dalek::SigningKey);
+
+impl SigningKey {
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::from(self.0.verifying_key())
+    }
+
+    pub fn issuer_id(&self) -> IssuerID {
+        IssuerID::from(&self.public_key())
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        Signature::from(self.0.sign(message))
+    }
+}
+
+impl From<[u8; 32]> for SigningKey {
+    fn from(seed: [u8; 32]) -> Self {
+        Self(ed25519_dalek::SigningKey::from_bytes(&seed))
+    }
+}
+
+/// Parses the seed of the key from 64 hex digits (optionally prefixed with `0x`).
+impl FromStr for SigningKey {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_hex(s).map(Self::from)
+    }
+}
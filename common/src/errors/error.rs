@@ -1,6 +1,6 @@
 use std::{backtrace::Backtrace, fmt};
 
-use crate::ids::BlockID;
+use crate::ids::{BlockID, IssuerID};
 
 pub enum Error {
     BlockNotFound {
@@ -13,6 +13,65 @@ pub enum Error {
         metadata: &'static str,
         backtrace: Backtrace,
     },
+
+    UnsupportedVersion {
+        expected: u8,
+        found: u8,
+        backtrace: Backtrace,
+    },
+
+    TrailingBytes {
+        count: usize,
+        backtrace: Backtrace,
+    },
+
+    DecodingFailed {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    BlockIDMismatch {
+        expected: BlockID,
+        actual: BlockID,
+        backtrace: Backtrace,
+    },
+
+    UnexpectedGenesisBlock {
+        block_id: BlockID,
+        backtrace: Backtrace,
+    },
+
+    InvalidPublicKey {
+        issuer_id: IssuerID,
+        backtrace: Backtrace,
+    },
+
+    InvalidSignature {
+        block_id: BlockID,
+        issuer_id: IssuerID,
+        backtrace: Backtrace,
+    },
+
+    InvalidPayload {
+        block_id: BlockID,
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    StorageFailed {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    BlockPruned {
+        block_id: BlockID,
+        backtrace: Backtrace,
+    },
+
+    InvalidEvidence {
+        reason: &'static str,
+        backtrace: Backtrace,
+    },
 }
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -38,6 +97,96 @@ impl fmt::Display for Error {
                     metadata, block_id, backtrace
                 )
             }
+            Error::UnsupportedVersion {
+                expected,
+                found,
+                backtrace,
+            } => {
+                write!(
+                    f,
+                    "Unsupported version `{}` (expected `{}`)\nBacktrace:\n{}",
+                    found, expected, backtrace
+                )
+            }
+            Error::TrailingBytes { count, backtrace } => {
+                write!(
+                    f,
+                    "Found {} trailing bytes after decoded value\nBacktrace:\n{}",
+                    count, backtrace
+                )
+            }
+            Error::DecodingFailed { reason, backtrace } => {
+                write!(f, "Decoding failed: {}\nBacktrace:\n{}", reason, backtrace)
+            }
+            Error::BlockIDMismatch {
+                expected,
+                actual,
+                backtrace,
+            } => {
+                write!(
+                    f,
+                    "Block ID mismatch: encoded `{}` but content hashes to `{}`\nBacktrace:\n{}",
+                    expected, actual, backtrace
+                )
+            }
+            Error::UnexpectedGenesisBlock {
+                block_id,
+                backtrace,
+            } => {
+                write!(
+                    f,
+                    "Genesis block `{}` can not be decoded from the wire\nBacktrace:\n{}",
+                    block_id, backtrace
+                )
+            }
+            Error::InvalidPublicKey {
+                issuer_id,
+                backtrace,
+            } => {
+                write!(
+                    f,
+                    "Issuer `{}` is not a valid public key\nBacktrace:\n{}",
+                    issuer_id, backtrace
+                )
+            }
+            Error::InvalidSignature {
+                block_id,
+                issuer_id,
+                backtrace,
+            } => {
+                write!(
+                    f,
+                    "Invalid signature of issuer `{}` on block `{}`\nBacktrace:\n{}",
+                    issuer_id, block_id, backtrace
+                )
+            }
+            Error::InvalidPayload {
+                block_id,
+                reason,
+                backtrace,
+            } => {
+                write!(
+                    f,
+                    "Invalid payload in block `{}`: {}\nBacktrace:\n{}",
+                    block_id, reason, backtrace
+                )
+            }
+            Error::StorageFailed { reason, backtrace } => {
+                write!(f, "Storage failed: {}\nBacktrace:\n{}", reason, backtrace)
+            }
+            Error::BlockPruned {
+                block_id,
+                backtrace,
+            } => {
+                write!(
+                    f,
+                    "Block `{}` was pruned\nBacktrace:\n{}",
+                    block_id, backtrace
+                )
+            }
+            Error::InvalidEvidence { reason, backtrace } => {
+                write!(f, "Invalid evidence: {}\nBacktrace:\n{}", reason, backtrace)
+            }
         }
     }
 }
@@ -4,12 +4,17 @@ use std::{
     hash::Hash,
     marker::PhantomData,
     ops::Deref,
+    str::FromStr,
     sync::Arc,
 };
 
 use serde::{Deserialize, Serialize};
 
-use crate::hash::{Hashable, Hasher};
+use crate::{
+    codec::parse_hex,
+    errors::{Error, Result},
+    hash::{Hashable, Hasher},
+};
 
 #[derive(Deserialize, Serialize)]
 pub struct Id<H: Hasher>(Arc<[u8; 32]>, PhantomData<H>);
@@ -75,6 +80,15 @@ impl<T: Hasher> Debug for Id<T> {
     }
 }
 
+/// Parses the 64 hex digits (optionally prefixed with `0x`) that the [`Debug`] output consists of.
+impl<H: Hasher> FromStr for Id<H> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_hex(s).map(Self::from)
+    }
+}
+
 impl<H: Hasher> Clone for Id<H> {
     fn clone(&self) -> Self {
         Id(Arc::clone(&self.0), PhantomData)
@@ -97,6 +111,18 @@ impl<H: Hasher> PartialEq for Id<H> {
 
 impl<H: Hasher> Eq for Id<H> {}
 
+impl<H: Hasher> Ord for Id<H> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<H: Hasher> PartialOrd for Id<H> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<H: Hasher> Hash for Id<H> {
     fn hash<T: hash::Hasher>(&self, state: &mut T) {
         self.0.hash(state);
@@ -1,9 +1,11 @@
 pub mod bft {
     mod committee;
+    mod equivocation;
     mod member;
     mod members;
 
     pub use committee::Committee;
+    pub use equivocation::Equivocation;
     pub use member::Member;
     pub use members::Members;
 }
@@ -18,6 +20,14 @@ pub mod blocks {
     pub use block_metadata_ref::BlockMetadataRef;
     pub use network_block::NetworkBlock;
 }
+pub mod codec {
+    mod hex;
+    mod versioned;
+
+    pub use hex::deserialize_hex;
+    pub(crate) use hex::parse_hex;
+    pub use versioned::*;
+}
 pub mod collections {
     mod any_map;
     mod max_set;
@@ -25,6 +35,15 @@ pub mod collections {
     pub use any_map::AnyMap;
     pub use max_set::MaxSet;
 }
+pub mod crypto {
+    mod public_key;
+    mod signature;
+    mod signing_key;
+
+    pub use public_key::PublicKey;
+    pub use signature::Signature;
+    pub use signing_key::SigningKey;
+}
 pub mod extensions {
     mod arc;
 
@@ -57,10 +76,14 @@ pub mod ids {
 }
 pub mod networking {
     mod endpoint;
+    mod message;
     mod network;
+    mod peer_id;
 
     pub use endpoint::*;
+    pub use message::*;
     pub use network::*;
+    pub use peer_id::*;
 }
 pub mod rx {
     mod callback;
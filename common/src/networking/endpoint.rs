@@ -1,8 +1,31 @@
-use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::{
+    mpsc::{UnboundedReceiver, UnboundedSender},
+    watch,
+};
 
-use crate::blocks::Block;
+use crate::networking::{Message, PeerID};
 
 pub struct Endpoint {
-    pub inbound: UnboundedReceiver<Block>,
-    pub outbound: UnboundedSender<Block>,
+    /// The messages received from peers, together with the peer that sent them.
+    pub inbound: UnboundedReceiver<(PeerID, Message)>,
+    /// The messages to send, together with the peers to send them to.
+    pub outbound: UnboundedSender<(Recipients, Message)>,
+    /// The peers that messages can currently be sent to.
+    pub peers: watch::Receiver<Vec<PeerID>>,
+}
+
+/// The peers that an outbound message is sent to.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Recipients {
+    All,
+    Peers(Vec<PeerID>),
+}
+
+impl Recipients {
+    pub fn includes(&self, peer: &PeerID) -> bool {
+        match self {
+            Recipients::All => true,
+            Recipients::Peers(peers) => peers.contains(peer),
+        }
+    }
 }
@@ -0,0 +1,85 @@
+use std::fmt::{self, Debug};
+
+use crate::{bft::Equivocation, blocks::Block, codec, errors::Result, ids::BlockID};
+
+/// A message exchanged between peers over a [`Network`](crate::networking::Network).
+#[derive(Clone)]
+pub enum Message {
+    /// A block that is pushed to the peers.
+    Block(Block),
+    /// The id of a block that the sender has, so that peers that miss it can request it.
+    BlockAnnouncement(BlockID),
+    /// A request for the block with the given id.
+    BlockRequest(BlockID),
+    /// A block that is sent in response to a [`Message::BlockRequest`].
+    BlockResponse(Block),
+    /// Evidence of a committee member that issued two conflicting milestones.
+    Equivocation(Equivocation),
+}
+
+impl Message {
+    pub const VERSION: u8 = 2;
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        codec::encode(Self::VERSION, &serialization::WireMessage::from(self))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(match codec::decode(Self::VERSION, bytes)? {
+            serialization::WireMessage::Block(bytes) => Message::Block(Block::from_bytes(&bytes)?),
+            serialization::WireMessage::BlockAnnouncement(id) => Message::BlockAnnouncement(id),
+            serialization::WireMessage::BlockRequest(id) => Message::BlockRequest(id),
+            serialization::WireMessage::BlockResponse(bytes) => {
+                Message::BlockResponse(Block::from_bytes(&bytes)?)
+            }
+            serialization::WireMessage::Equivocation(first, second) => Message::Equivocation(
+                Equivocation::new(Block::from_bytes(&first)?, Block::from_bytes(&second)?)?,
+            ),
+        })
+    }
+}
+
+impl Debug for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self {
+            Message::Block(block) => write!(f, "Block({:?})", block.id()),
+            Message::BlockAnnouncement(id) => write!(f, "BlockAnnouncement({:?})", id),
+            Message::BlockRequest(id) => write!(f, "BlockRequest({:?})", id),
+            Message::BlockResponse(block) => write!(f, "BlockResponse({:?})", block.id()),
+            Message::Equivocation(equivocation) => write!(f, "{:?}", equivocation),
+        }
+    }
+}
+
+mod serialization {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{ids::BlockID, networking::Message};
+
+    /// Wire representation of a [`Message`] that carries blocks in their encoded form, so that
+    /// decoders verify their IDs.
+    #[derive(Deserialize, Serialize)]
+    pub(super) enum WireMessage {
+        Block(Vec<u8>),
+        BlockAnnouncement(BlockID),
+        BlockRequest(BlockID),
+        BlockResponse(Vec<u8>),
+        Equivocation(Vec<u8>, Vec<u8>),
+        // variants are appended, so that the encoding of the existing ones stays stable
+    }
+
+    impl From<&Message> for WireMessage {
+        fn from(message: &Message) -> Self {
+            match message {
+                Message::Block(block) => WireMessage::Block(block.to_bytes()),
+                Message::BlockAnnouncement(id) => WireMessage::BlockAnnouncement(id.clone()),
+                Message::BlockRequest(id) => WireMessage::BlockRequest(id.clone()),
+                Message::BlockResponse(block) => WireMessage::BlockResponse(block.to_bytes()),
+                Message::Equivocation(equivocation) => {
+                    let [first, second] = equivocation.blocks();
+                    WireMessage::Equivocation(first.to_bytes(), second.to_bytes())
+                }
+            }
+        }
+    }
+}
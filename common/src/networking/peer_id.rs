@@ -0,0 +1,13 @@
+use std::fmt;
+
+/// Identifies a peer of an [`Endpoint`](crate::networking::Endpoint). IDs are assigned by the
+/// [`Network`](crate::networking::Network) and are only meaningful to the endpoint they were
+/// received from.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct PeerID(pub u64);
+
+impl fmt::Display for PeerID {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "peer-{}", self.0)
+    }
+}
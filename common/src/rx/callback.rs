@@ -1,6 +1,6 @@
 use std::sync::Mutex;
 
-use slotmap::HopSlotMap;
+use slotmap::SlotMap;
 use trait_set::trait_set;
 
 use crate::rx::subscription::{ID, Unsubscribable};
@@ -11,7 +11,7 @@ trait_set! {
     pub trait CallbackOnce<T> = FnOnce(&T) + Send + Sync + 'static;
 }
 
-pub type Callbacks<T> = Mutex<HopSlotMap<ID, Box<dyn Callback<T>>>>;
+pub type Callbacks<T> = Mutex<SlotMap<ID, Box<dyn Callback<T>>>>;
 
 impl<T> Unsubscribable for Callbacks<T> {
     fn unsubscribe(&self, key: ID) {
@@ -19,7 +19,7 @@ impl<T> Unsubscribable for Callbacks<T> {
     }
 }
 
-pub type CallbacksOnce<T> = Mutex<HopSlotMap<ID, Box<dyn CallbackOnce<T>>>>;
+pub type CallbacksOnce<T> = Mutex<SlotMap<ID, Box<dyn CallbackOnce<T>>>>;
 
 impl<T> Unsubscribable for CallbacksOnce<T> {
     fn unsubscribe(&self, key: ID) {
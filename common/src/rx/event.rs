@@ -1,6 +1,6 @@
 use std::sync::{Arc, Mutex};
 
-use slotmap::HopSlotMap;
+use slotmap::SlotMap;
 
 use crate::rx::{
     callback::{Callback, Callbacks},
@@ -12,7 +12,7 @@ pub struct Event<T>(Arc<Callbacks<T>>);
 
 impl<T> Event<T> {
     pub fn new() -> Self {
-        Self(Arc::new(Mutex::new(HopSlotMap::with_key())))
+        Self(Arc::new(Mutex::new(SlotMap::with_key())))
     }
 
     pub fn subscribe(&self, callback: impl Callback<T>) -> Subscription<Callbacks<T>> {
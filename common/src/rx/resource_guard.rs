@@ -39,10 +39,10 @@ impl<T> Deref for ResourceGuard<T> {
 
 impl<T> Drop for ResourceGuard<T> {
     fn drop(&mut self) {
-        if let Some(inner) = Arc::get_mut(&mut self.0) {
-            if let Some(callback) = inner.done_callback.take() {
-                callback(&inner.value);
-            }
+        if let Some(inner) = Arc::get_mut(&mut self.0)
+            && let Some(callback) = inner.done_callback.take()
+        {
+            callback(&inner.value);
         }
     }
 }
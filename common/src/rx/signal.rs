@@ -1,40 +1,51 @@
 use std::sync::{Arc, Mutex, MutexGuard};
 
-use slotmap::HopSlotMap;
+use slotmap::SlotMap;
 
 use crate::rx::{
     callback::{CallbackOnce, CallbacksOnce},
     subscription::{ID, Subscription},
 };
 
+/// A value that is set at most once. Callbacks are executed without holding the lock of the value,
+/// so that they can read the signal that triggered them.
 pub struct Signal<T> {
     signal: Mutex<Option<T>>,
     callbacks: Arc<CallbacksOnce<T>>,
 }
 
 impl<T> Signal<T> {
-    pub fn set(&self, signal: T) {
-        drop(self.get_or_insert(signal));
+    pub fn get(&self) -> MutexGuard<'_, Option<T>> {
+        self.signal.lock().unwrap()
     }
+}
 
-    pub fn get(&self) -> MutexGuard<Option<T>> {
-        self.signal.lock().unwrap()
+impl<T: Clone> Signal<T> {
+    pub fn set(&self, signal: T) {
+        drop(self.get_or_insert(signal));
     }
 
-    pub fn get_or_insert(&self, default: T) -> MutexGuard<Option<T>> {
+    pub fn get_or_insert(&self, default: T) -> MutexGuard<'_, Option<T>> {
         self.get_or_insert_with(|| default)
     }
 
-    pub fn get_or_insert_with(&self, default: impl FnOnce() -> T) -> MutexGuard<Option<T>> {
-        let mut value = self.signal.lock().unwrap();
-        if value.is_none() {
-            let signal = default();
-            for (_, callback) in self.callbacks.lock().unwrap().drain() {
-                callback(&signal);
+    pub fn get_or_insert_with(&self, default: impl FnOnce() -> T) -> MutexGuard<'_, Option<T>> {
+        let (signal, callbacks) = {
+            let mut value = self.signal.lock().unwrap();
+            if value.is_some() {
+                return value;
             }
-            *value = Some(signal);
+
+            let signal = value.insert(default()).clone();
+            let callbacks: Vec<_> = self.callbacks.lock().unwrap().drain().collect();
+            (signal, callbacks)
+        };
+
+        for (_, callback) in callbacks {
+            callback(&signal);
         }
-        value
+
+        self.signal.lock().unwrap()
     }
 
     pub fn subscribe(&self, callback: impl CallbackOnce<T>) -> Subscription<CallbacksOnce<T>> {
@@ -52,20 +63,21 @@ impl<T> Signal<T> {
         .retain()
     }
 
+    pub fn value(&self) -> Option<T> {
+        self.get().as_ref().cloned()
+    }
+
     fn try_add_callback(&self, callback: impl CallbackOnce<T>) -> Option<ID> {
-        match self.signal.lock().unwrap().as_ref() {
-            Some(emitted_signal) => {
-                callback(emitted_signal);
-                None
+        let emitted_signal = {
+            let value = self.signal.lock().unwrap();
+            match value.as_ref() {
+                Some(emitted_signal) => emitted_signal.clone(),
+                None => return Some(self.callbacks.lock().unwrap().insert(Box::new(callback))),
             }
-            None => Some(self.callbacks.lock().unwrap().insert(Box::new(callback))),
-        }
-    }
-}
+        };
 
-impl<T: Clone> Signal<T> {
-    pub fn value(&self) -> Option<T> {
-        self.get().as_ref().cloned()
+        callback(&emitted_signal);
+        None
     }
 }
 
@@ -73,7 +85,7 @@ impl<T> Default for Signal<T> {
     fn default() -> Self {
         Self {
             signal: Mutex::new(None),
-            callbacks: Arc::new(Mutex::new(HopSlotMap::with_key())),
+            callbacks: Arc::new(Mutex::new(SlotMap::with_key())),
         }
     }
 }
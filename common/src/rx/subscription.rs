@@ -28,10 +28,10 @@ pub trait Unsubscribable {
 
 impl<T: Unsubscribable> Drop for Subscription<T> {
     fn drop(&mut self) {
-        if let Some(id) = self.id.take() {
-            if let Some(emitter) = self.callbacks.upgrade() {
-                emitter.unsubscribe(id);
-            }
+        if let Some(id) = self.id.take()
+            && let Some(emitter) = self.callbacks.upgrade()
+        {
+            emitter.unsubscribe(id);
         }
     }
 }
@@ -41,7 +41,7 @@ impl<T> Variable<T> {
         });
     }
 
-    pub fn get(&self) -> MutexGuard<Option<T>> {
+    pub fn get(&self) -> MutexGuard<'_, Option<T>> {
         self.value.lock().unwrap()
     }
 
@@ -53,11 +53,11 @@ impl<T> Variable<T> {
         f(self.value.lock().unwrap().as_ref().unwrap())
     }
 
-    pub fn get_or_insert(&self, default: T) -> MutexGuard<Option<T>> {
+    pub fn get_or_insert(&self, default: T) -> MutexGuard<'_, Option<T>> {
         self.compute_if_none(|| self.process_update(None, Some(default)))
     }
 
-    pub fn get_or_insert_with(&self, default: impl FnOnce() -> T) -> MutexGuard<Option<T>> {
+    pub fn get_or_insert_with(&self, default: impl FnOnce() -> T) -> MutexGuard<'_, Option<T>> {
         self.compute_if_none(|| self.process_update(None, Some(default())))
     }
 
@@ -101,7 +101,7 @@ impl<T> Variable<T> {
         Ok(())
     }
 
-    fn compute_if_some<F: FnOnce(T) -> Option<T>>(&self, compute: F) -> MutexGuard<Option<T>> {
+    fn compute_if_some<F: FnOnce(T) -> Option<T>>(&self, compute: F) -> MutexGuard<'_, Option<T>> {
         let mut value = self.get();
         if value.is_some() {
             *value = compute(value.take().unwrap());
@@ -109,7 +109,7 @@ impl<T> Variable<T> {
         value
     }
 
-    fn compute_if_none<F: FnOnce() -> Option<T>>(&self, compute: F) -> MutexGuard<Option<T>> {
+    fn compute_if_none<F: FnOnce() -> Option<T>>(&self, compute: F) -> MutexGuard<'_, Option<T>> {
         let mut value = self.get();
         if value.is_none() {
             *value = compute();
@@ -1,7 +1,19 @@
+use std::{any::Any, fmt};
+
 use tokio::task::JoinHandle;
 use tracing::{Instrument, Span, error, info};
 
-pub fn worker<F: Future<Output = ()> + Send + 'static>(fut: F, span: Span) -> JoinHandle<()> {
+use crate::rx::Event;
+
+/// Spawns a task that logs when it starts and stops. Panics of the task are logged and reported to
+/// `failures`.
+pub fn worker<F: Future<Output = ()> + Send + 'static>(
+    fut: F,
+    span: Span,
+    failures: &Event<WorkerFailure>,
+) -> JoinHandle<()> {
+    let worker = span.metadata().map_or("worker", |metadata| metadata.name());
+    let failures = failures.clone();
     tokio::spawn(
         async move {
             if let Err(e) = tokio::spawn(
@@ -15,8 +27,39 @@ pub fn worker<F: Future<Output = ()> + Send + 'static>(fut: F, span: Span) -> Jo
             .await
             {
                 error!("worker panicked: {:?}", e);
+                if e.is_panic() {
+                    failures.trigger(&WorkerFailure {
+                        worker,
+                        reason: panic_reason(e.into_panic()),
+                    });
+                }
             }
         }
         .instrument(span),
     )
 }
+
+/// A worker that stopped because it panicked.
+#[derive(Clone, Debug)]
+pub struct WorkerFailure {
+    /// The name of the span of the worker (or `worker` if the span is disabled).
+    pub worker: &'static str,
+    pub reason: String,
+}
+
+impl fmt::Display for WorkerFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "worker `{}` panicked: {}", self.worker, self.reason)
+    }
+}
+
+/// Returns the message of a panic (if it has one).
+pub fn panic_reason(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
@@ -0,0 +1,84 @@
+use common::{
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    errors::Error,
+    ids::BlockID,
+};
+
+fn network_block() -> Block {
+    Block::from(NetworkBlock::new(
+        vec![BlockID::default(), BlockID::from([7; 32])],
+        1,
+        b"payload".to_vec(),
+        &SigningKey::from([1; 32]),
+    ))
+}
+
+#[test]
+fn test_round_trip() {
+    let block = network_block();
+    let bytes = block.to_bytes();
+
+    let decoded = Block::from_bytes(&bytes).expect("must decode");
+    assert_eq!(decoded.id(), block.id());
+    assert_eq!(decoded.parents(), block.parents());
+    assert_eq!(decoded.payload(), b"payload");
+    assert_eq!(decoded.to_bytes(), bytes);
+}
+
+#[test]
+fn test_rejects_genesis_block() {
+    let genesis = Block::GenesisBlock(BlockID::from([7; 32]));
+
+    assert!(matches!(
+        Block::from_bytes(&genesis.to_bytes()),
+        Err(Error::UnexpectedGenesisBlock { block_id, .. }) if block_id == BlockID::from([7; 32])
+    ));
+}
+
+#[test]
+fn test_network_block_round_trip() {
+    let Block::NetworkBlock(_, network_block) = network_block() else {
+        unreachable!()
+    };
+
+    let decoded = NetworkBlock::from_bytes(&network_block.to_bytes()).expect("must decode");
+    assert_eq!(decoded.parents, network_block.parents);
+    assert_eq!(decoded.issuer_id, network_block.issuer_id);
+    assert!(decoded.signature == network_block.signature);
+}
+
+#[test]
+fn test_rejects_unknown_version() {
+    let mut bytes = network_block().to_bytes();
+    bytes[0] = Block::VERSION + 1;
+
+    assert!(matches!(
+        Block::from_bytes(&bytes),
+        Err(Error::UnsupportedVersion { .. })
+    ));
+}
+
+#[test]
+fn test_rejects_trailing_bytes() {
+    let mut bytes = network_block().to_bytes();
+    bytes.push(0);
+
+    assert!(matches!(
+        Block::from_bytes(&bytes),
+        Err(Error::TrailingBytes { count: 1, .. })
+    ));
+}
+
+#[test]
+fn test_rejects_mismatching_id() {
+    let Block::NetworkBlock(_, network_block) = network_block() else {
+        unreachable!()
+    };
+    let forged = Block::NetworkBlock(BlockID::from([9; 32]), network_block);
+
+    assert!(matches!(
+        Block::from_bytes(&forged.to_bytes()),
+        Err(Error::BlockIDMismatch { .. })
+    ));
+}
@@ -0,0 +1,113 @@
+use common::{
+    blocks::{Block, NetworkBlock},
+    crypto::{Signature, SigningKey},
+    errors::Error,
+    ids::{BlockID, IssuerID},
+};
+
+#[test]
+fn test_valid_signature() {
+    let signing_key = SigningKey::from([1; 32]);
+    let block = Block::from(NetworkBlock::new(
+        vec![BlockID::default()],
+        1,
+        vec![],
+        &signing_key,
+    ));
+
+    assert!(block.verify_signature().is_ok());
+    assert!(matches!(&block, Block::NetworkBlock(_, b) if b.issuer_id == signing_key.issuer_id()));
+}
+
+#[test]
+fn test_forged_issuer() {
+    let mut network_block = NetworkBlock::new(
+        vec![BlockID::default()],
+        1,
+        vec![],
+        &SigningKey::from([1; 32]),
+    );
+    network_block.issuer_id = SigningKey::from([2; 32]).issuer_id();
+
+    assert!(matches!(
+        Block::from(network_block).verify_signature(),
+        Err(Error::InvalidSignature { .. })
+    ));
+}
+
+#[test]
+fn test_tampered_parents() {
+    let mut network_block = NetworkBlock::new(
+        vec![BlockID::default()],
+        1,
+        vec![],
+        &SigningKey::from([1; 32]),
+    );
+    network_block.parents.push(BlockID::from([3; 32]));
+
+    assert!(matches!(
+        Block::from(network_block).verify_signature(),
+        Err(Error::InvalidSignature { .. })
+    ));
+}
+
+#[test]
+fn test_tampered_issuing_time() {
+    let mut network_block = NetworkBlock::new(
+        vec![BlockID::default()],
+        1,
+        vec![],
+        &SigningKey::from([1; 32]),
+    );
+    network_block.issuing_time = 2;
+
+    assert!(matches!(
+        Block::from(network_block).verify_signature(),
+        Err(Error::InvalidSignature { .. })
+    ));
+}
+
+#[test]
+fn test_tampered_payload() {
+    let mut network_block = NetworkBlock::new(
+        vec![BlockID::default()],
+        1,
+        b"transfer 1".to_vec(),
+        &SigningKey::from([1; 32]),
+    );
+    network_block.payload = b"transfer 1000".to_vec();
+
+    assert!(matches!(
+        Block::from(network_block).verify_signature(),
+        Err(Error::InvalidSignature { .. })
+    ));
+}
+
+#[test]
+fn test_missing_signature() {
+    let network_block = NetworkBlock {
+        parents: vec![BlockID::default()],
+        issuer_id: SigningKey::from([1; 32]).issuer_id(),
+        issuing_time: 1,
+        payload: vec![],
+        signature: Signature::default(),
+    };
+
+    assert!(Block::from(network_block).verify_signature().is_err());
+}
+
+#[test]
+fn test_invalid_public_key() {
+    let network_block = NetworkBlock {
+        parents: vec![BlockID::default()],
+        issuer_id: IssuerID::from([2; 32]),
+        issuing_time: 1,
+        payload: vec![],
+        signature: Signature::default(),
+    };
+
+    assert!(matches!(
+        Block::from(network_block).verify_signature(),
+        Err(Error::InvalidPublicKey { issuer_id, .. }) if issuer_id == IssuerID::from([2; 32])
+    ));
+}
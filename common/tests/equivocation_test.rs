@@ -0,0 +1,58 @@
+use common::{
+    bft::Equivocation,
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    errors::Error,
+    ids::Id,
+};
+
+fn block(signer: u8, issuing_time: u64) -> Block {
+    NetworkBlock::new(
+        vec![Id::default()],
+        issuing_time,
+        vec![],
+        &SigningKey::from([signer; 32]),
+    )
+    .into()
+}
+
+#[test]
+fn test_evidence_is_independent_of_the_order() {
+    let (first, second) = (block(1, 1), block(1, 2));
+
+    let evidence = Equivocation::new(first.clone(), second.clone()).unwrap();
+    let reversed = Equivocation::new(second, first).unwrap();
+
+    assert_eq!(evidence.issuer_id(), &SigningKey::from([1; 32]).issuer_id());
+    assert_eq!(
+        evidence.blocks().each_ref().map(|block| block.id().clone()),
+        reversed.blocks().each_ref().map(|block| block.id().clone())
+    );
+    assert!(evidence.verify().is_ok());
+}
+
+#[test]
+fn test_invalid_evidence() {
+    assert!(matches!(
+        Equivocation::new(block(1, 1), block(1, 1)),
+        Err(Error::InvalidEvidence { .. })
+    ));
+    assert!(matches!(
+        Equivocation::new(block(1, 1), block(2, 1)),
+        Err(Error::InvalidEvidence { .. })
+    ));
+    assert!(matches!(
+        Equivocation::new(Block::GenesisBlock(Id::default()), block(1, 1)),
+        Err(Error::InvalidEvidence { .. })
+    ));
+
+    // blocks whose signature does not match the accused issuer are no evidence
+    let Block::NetworkBlock(_, mut forged) = block(1, 2) else {
+        unreachable!()
+    };
+    forged.signature = SigningKey::from([2; 32]).sign(&[0; 32]);
+    assert!(matches!(
+        Equivocation::new(block(1, 1), forged.into()),
+        Err(Error::InvalidSignature { .. })
+    ));
+}
@@ -0,0 +1,53 @@
+use common::{
+    bft::Equivocation,
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    errors::Error,
+    ids::Id,
+    networking::Message,
+};
+
+fn block() -> Block {
+    NetworkBlock::new(vec![Id::default()], 1, vec![], &SigningKey::from([1; 32])).into()
+}
+
+fn other_block() -> Block {
+    NetworkBlock::new(vec![Id::default()], 2, vec![], &SigningKey::from([1; 32])).into()
+}
+
+#[test]
+fn test_round_trip() {
+    let messages = [
+        Message::Block(block()),
+        Message::BlockAnnouncement(Id::default()),
+        Message::BlockRequest(Id::default()),
+        Message::BlockResponse(block()),
+        Message::Equivocation(Equivocation::new(block(), other_block()).unwrap()),
+    ];
+
+    for message in messages {
+        let decoded = Message::from_bytes(&message.to_bytes()).unwrap();
+        assert_eq!(format!("{:?}", decoded), format!("{:?}", message));
+    }
+}
+
+#[test]
+fn test_genesis_block() {
+    let message = Message::Block(Block::GenesisBlock(Id::default()));
+
+    assert!(matches!(
+        Message::from_bytes(&message.to_bytes()),
+        Err(Error::UnexpectedGenesisBlock { .. })
+    ));
+}
+
+#[test]
+fn test_unsupported_version() {
+    let mut bytes = Message::BlockRequest(Id::default()).to_bytes();
+    bytes[0] = Message::VERSION + 1;
+
+    assert!(matches!(
+        Message::from_bytes(&bytes),
+        Err(Error::UnsupportedVersion { .. })
+    ));
+}
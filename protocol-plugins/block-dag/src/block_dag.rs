@@ -1,22 +1,28 @@
-use std::sync::{Arc, Mutex, Weak};
+use std::sync::{Arc, Mutex, RwLock, Weak};
 
 use async_trait::async_trait;
 use block_storage::{Address, BlockStorage};
 use common::{
-    blocks::BlockMetadata,
+    blocks::{Block, BlockMetadata},
     down,
+    errors::Error,
     extensions::ArcExt,
+    ids::BlockID,
     rx::{Callbacks, Event, Subscription},
     up, with,
 };
 use protocol::{ManagedPlugin, Plugins};
-use tracing::{Span, info, info_span, trace};
+use tracing::{Span, info, info_span, trace, warn};
 
-use crate::BlockDAGMetadata;
+use crate::{BlockDAGMetadata, PayloadValidator};
 
 pub struct BlockDAG {
     pub block_available: Event<BlockMetadata>,
-    block_storage_subscription: Mutex<Option<Subscription<Callbacks<Address>>>>,
+    /// Triggered for blocks whose payload was rejected by a validator. They (and their future
+    /// cone) never become available.
+    pub payload_rejected: Event<BlockMetadata>,
+    payload_validators: RwLock<Vec<Arc<dyn PayloadValidator>>>,
+    block_storage_subscription: Mutex<Option<AddressSubscription>>,
     block_storage: Arc<BlockStorage>,
     span: Span,
 }
@@ -29,8 +35,10 @@ impl ManagedPlugin for BlockDAG {
 
             Self {
                 block_available: Event::default(),
+                payload_rejected: Event::default(),
+                payload_validators: Default::default(),
                 block_storage_subscription: Mutex::new(Some(block_storage.new_address.subscribe(
-                    with!(this: move |address| {
+                    with!(this: move |(_, address)| {
                         address.attach(with!(this: move |block| up!(this: {
                             this.provide_metadata(block)
                         })))
@@ -54,22 +62,58 @@ impl ManagedPlugin for BlockDAG {
 }
 
 impl BlockDAG {
+    pub fn register_payload_validator(&self, validator: Arc<dyn PayloadValidator>) {
+        self.payload_validators.write().unwrap().push(validator);
+    }
+
     fn provide_metadata(self: Arc<Self>, block: &BlockMetadata) {
         let metadata = block.set(Arc::new(BlockDAGMetadata::new(block.block.parents().len())));
 
         metadata.all_parents_available.attach({
             let this = self.downgrade();
-            down!(block: move |_| up!(this, block: {
-                this.block_available.trigger(&block)
+            down!(block, metadata: move |_| up!(this, block, metadata: {
+                if this.is_payload_valid(&block) {
+                    this.block_available.trigger(&block);
+                    metadata.available.set(());
+                } else {
+                    this.payload_rejected.trigger(&block);
+                }
             }))
         });
 
         for (index, parent_id) in block.block.parents().iter().enumerate() {
-            self.block_storage.address(parent_id).attach(
-                down!(metadata: move |parent| up!(metadata: {
+            match self.block_storage.address(parent_id) {
+                Ok(address) => address.attach(down!(metadata: move |parent| up!(metadata: {
                     metadata.register_parent(index, parent)
-                })),
-            );
+                }))),
+                // blocks that reference pruned history never become available
+                Err(err) => {
+                    warn!(block_id = %block.block.id(), reason = %err, "references pruned history");
+                    return;
+                }
+            }
+        }
+    }
+
+    fn is_payload_valid(&self, block: &BlockMetadata) -> bool {
+        if let Block::GenesisBlock(_) = block.block {
+            return true;
         }
+
+        for validator in self.payload_validators.read().unwrap().iter() {
+            match validator.validate(block) {
+                Ok(()) => continue,
+                Err(Error::InvalidPayload { reason, .. }) => {
+                    warn!(block_id = %block.block.id(), reason, "payload rejected")
+                }
+                Err(err) => warn!(block_id = %block.block.id(), reason = %err, "payload rejected"),
+            }
+
+            return false;
+        }
+
+        true
     }
 }
+
+type AddressSubscription = Subscription<Callbacks<(BlockID, Address)>>;
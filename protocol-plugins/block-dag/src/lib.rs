@@ -1,5 +1,6 @@
 mod block_dag;
 mod extensions;
 mod metadata;
+mod payload_validator;
 
-pub use crate::{block_dag::*, extensions::*, metadata::*};
+pub use crate::{block_dag::*, extensions::*, metadata::*, payload_validator::*};
@@ -3,12 +3,13 @@ use std::sync::{Arc, RwLock, RwLockReadGuard};
 use common::{
     blocks::{BlockMetadata, BlockMetadataRef},
     extensions::ArcExt,
-    rx::Countdown,
+    rx::{Countdown, Signal},
     up,
 };
 
 pub struct BlockDAGMetadata {
     pub all_parents_available: Countdown,
+    pub available: Signal<()>,
     parents: RwLock<Vec<BlockMetadataRef>>,
 }
 
@@ -17,6 +18,7 @@ impl BlockDAGMetadata {
         Self {
             parents: RwLock::new(vec![BlockMetadataRef::default(); parents_count]),
             all_parents_available: Countdown::new(parents_count),
+            available: Signal::default(),
         }
     }
 
@@ -27,7 +29,7 @@ impl BlockDAGMetadata {
             let this = self.downgrade();
             |parent| {
                 parent
-                    .all_parents_available
+                    .available
                     .attach(move |_| up!(this: this.all_parents_available.decrease()))
             }
         });
@@ -35,7 +37,7 @@ impl BlockDAGMetadata {
 }
 
 impl BlockDAGMetadata {
-    pub fn parents(&self) -> RwLockReadGuard<Vec<BlockMetadataRef>> {
+    pub fn parents(&self) -> RwLockReadGuard<'_, Vec<BlockMetadataRef>> {
         self.parents.read().unwrap()
     }
 }
@@ -0,0 +1,10 @@
+use common::{blocks::BlockMetadata, errors::Result};
+
+/// Validates the payload of a block before the [`BlockDAG`](crate::BlockDAG) marks it as
+/// available.
+///
+/// Blocks whose payload is rejected never become available, and neither do their descendants.
+/// The genesis block carries no payload and is not validated.
+pub trait PayloadValidator: Send + Sync {
+    fn validate(&self, block: &BlockMetadata) -> Result<()>;
+}
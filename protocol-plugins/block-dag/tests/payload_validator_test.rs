@@ -0,0 +1,81 @@
+use std::{
+    backtrace::Backtrace,
+    sync::{Arc, Mutex},
+};
+
+use block_dag::{BlockDAG, PayloadValidator};
+use block_storage::BlockStorage;
+use common::{
+    blocks::{Block, BlockMetadata, NetworkBlock},
+    crypto::SigningKey,
+    errors::{Error, Result},
+    ids::BlockID,
+};
+use protocol::Plugins;
+
+struct RejectEmptyPayloads;
+
+impl PayloadValidator for RejectEmptyPayloads {
+    fn validate(&self, block: &BlockMetadata) -> Result<()> {
+        match block.block.payload().is_empty() {
+            true => Err(Error::InvalidPayload {
+                block_id: block.block.id().clone(),
+                reason: "payload must not be empty".into(),
+                backtrace: Backtrace::capture(),
+            }),
+            false => Ok(()),
+        }
+    }
+}
+
+fn block(parents: Vec<BlockID>, payload: &[u8]) -> Block {
+    Block::from(NetworkBlock::new(
+        parents,
+        1,
+        payload.to_vec(),
+        &SigningKey::from([1; 32]),
+    ))
+}
+
+#[test]
+fn test_payload_validator() {
+    let mut plugins = Plugins::default();
+    let block_dag = plugins.load::<BlockDAG>();
+    let block_storage = plugins.load::<BlockStorage>();
+    block_dag.register_payload_validator(Arc::new(RejectEmptyPayloads));
+
+    let available = Arc::new(Mutex::new(Vec::new()));
+    let _subscription = block_dag.block_available.subscribe({
+        let available = available.clone();
+        move |block| available.lock().unwrap().push(block.block.id().clone())
+    });
+    let rejected = Arc::new(Mutex::new(Vec::new()));
+    let _rejections = block_dag.payload_rejected.subscribe({
+        let rejected = rejected.clone();
+        move |block| rejected.lock().unwrap().push(block.block.id().clone())
+    });
+
+    let genesis = block_storage
+        .insert(Block::GenesisBlock(BlockID::default()))
+        .unwrap();
+    let valid = block(vec![genesis.block.id().clone()], b"tx");
+    let invalid = block(vec![genesis.block.id().clone()], b"");
+    let child_of_invalid = block(vec![invalid.id().clone()], b"tx");
+    let child_of_valid = block(vec![valid.id().clone()], b"tx");
+
+    for block in [&valid, &invalid, &child_of_invalid, &child_of_valid] {
+        block_storage.insert(block.clone());
+    }
+
+    assert_eq!(
+        *available.lock().unwrap(),
+        vec![
+            genesis.block.id().clone(),
+            valid.id().clone(),
+            child_of_valid.id().clone(),
+        ]
+    );
+
+    // only the block with the invalid payload is reported, not its future cone
+    assert_eq!(*rejected.lock().unwrap(), vec![invalid.id().clone()]);
+}
@@ -0,0 +1,58 @@
+use std::sync::{Arc, Mutex};
+
+use block_dag::BlockDAG;
+use block_storage::BlockStorage;
+use common::{
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    errors::Error,
+    ids::BlockID,
+};
+use protocol::Plugins;
+
+fn block(parents: Vec<BlockID>, payload: &[u8]) -> Block {
+    Block::from(NetworkBlock::new(
+        parents,
+        1,
+        payload.to_vec(),
+        &SigningKey::from([1; 32]),
+    ))
+}
+
+#[test]
+fn test_references_into_pruned_history() {
+    let mut plugins = Plugins::default();
+    let block_dag = plugins.load::<BlockDAG>();
+    let block_storage = plugins.load::<BlockStorage>();
+
+    let available = Arc::new(Mutex::new(Vec::new()));
+    let _subscription = block_dag.block_available.subscribe({
+        let available = available.clone();
+        move |block| available.lock().unwrap().push(block.block.id().clone())
+    });
+
+    let genesis = block_storage
+        .insert(Block::GenesisBlock(BlockID::default()))
+        .unwrap();
+    let pruned = block(vec![genesis.block.id().clone()], b"pruned");
+    block_storage.insert(pruned.clone()).unwrap();
+
+    assert!(block_storage.evict(pruned.id()).is_some());
+    assert!(block_storage.get(pruned.id()).is_none());
+    assert!(block_storage.is_pruned(pruned.id()));
+
+    // the address of a pruned block is not allocated again
+    assert!(matches!(
+        block_storage.address(pruned.id()),
+        Err(Error::BlockPruned { ref block_id, .. }) if block_id == pruned.id()
+    ));
+    assert!(block_storage.insert(pruned.clone()).is_none());
+
+    // blocks that reference pruned history are stored but never become available
+    let child = block(vec![pruned.id().clone()], b"child");
+    assert!(block_storage.insert(child.clone()).is_some());
+    assert_eq!(
+        *available.lock().unwrap(),
+        vec![genesis.block.id().clone(), pruned.id().clone()]
+    );
+}
@@ -1,16 +1,21 @@
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
+use clock::ProtocolClock;
 use common::{
     blocks::{Block, NetworkBlock},
-    ids::IssuerID,
+    crypto::SigningKey,
 };
 use protocol::{ManagedPlugin, Plugins};
 use tip_selection::TipSelection;
 use tracing::{Span, info_span};
 use virtual_voting::VirtualVotingConfig;
 
+use crate::PayloadSource;
+
 pub struct BlockFactory<C: VirtualVotingConfig> {
     tip_selection: Arc<TipSelection<C>>,
+    clock: Arc<ProtocolClock>,
+    payload_source: RwLock<Option<Arc<dyn PayloadSource>>>,
     span: Span,
 }
 
@@ -18,6 +23,8 @@ impl<C: VirtualVotingConfig> ManagedPlugin for BlockFactory<C> {
     fn new(plugins: &mut Plugins) -> Arc<Self> {
         Arc::new(Self {
             tip_selection: plugins.load(),
+            clock: plugins.load(),
+            payload_source: RwLock::new(None),
             span: info_span!("block_factory"),
         })
     }
@@ -28,10 +35,24 @@ impl<C: VirtualVotingConfig> ManagedPlugin for BlockFactory<C> {
 }
 
 impl<C: VirtualVotingConfig> BlockFactory<C> {
-    pub fn create_block(&self, issuer: &IssuerID) -> Block {
-        Block::from(NetworkBlock {
-            parents: self.tip_selection.get(),
-            issuer_id: issuer.clone(),
-        })
+    pub fn create_block(&self, signing_key: &SigningKey) -> Block {
+        let payload = self
+            .payload_source
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|source| source.next_payload())
+            .unwrap_or_default();
+
+        Block::from(NetworkBlock::new(
+            self.tip_selection.get(),
+            self.clock.now(),
+            payload,
+            signing_key,
+        ))
+    }
+
+    pub fn set_payload_source(&self, payload_source: Arc<dyn PayloadSource>) {
+        *self.payload_source.write().unwrap() = Some(payload_source);
     }
 }
@@ -1,3 +1,4 @@
 mod block_factory;
+mod payload_source;
 
-pub use crate::block_factory::*;
+pub use crate::{block_factory::*, payload_source::*};
@@ -0,0 +1,6 @@
+/// Source of pending application data that the [`BlockFactory`](crate::BlockFactory) embeds into
+/// the blocks it creates (e.g. a mempool).
+pub trait PayloadSource: Send + Sync {
+    /// Takes the payload for the next block from the source.
+    fn next_payload(&self) -> Vec<u8>;
+}
@@ -1,38 +1,56 @@
 use std::{
-    collections::HashMap,
+    backtrace::Backtrace,
+    collections::{HashMap, HashSet},
     sync::{Arc, Mutex},
 };
 
 use async_trait::async_trait;
 use common::{
     blocks::{Block, Block::GenesisBlock, BlockMetadata},
+    errors::{Error, Result},
     ids::{BlockID, Id},
     rx::{Event, Signal},
 };
 use protocol::{ManagedPlugin, Plugins};
-use tracing::{Span, debug, info_span, trace};
+use tracing::{Span, debug, info_span, trace, warn};
 
-use crate::Address;
+use crate::{Address, BlockStore};
 
 pub struct BlockStorage {
-    pub new_address: Event<Address>,
+    /// Triggered when the address of a block is allocated, before the block is stored.
+    pub new_address: Event<(BlockID, Address)>,
     blocks: Mutex<HashMap<BlockID, Address>>,
+    /// The ids of evicted blocks, so that references into pruned history fail instead of
+    /// allocating addresses that are never filled. It is locked before `blocks`.
+    pruned: Mutex<HashSet<BlockID>>,
+    /// Serializes insertions, as the callbacks of an address (and the allocation of the addresses
+    /// of its parents) are not safe to interleave with the insertion of another block.
+    inserting: Mutex<()>,
+    store: Option<Arc<dyn BlockStore>>,
     span: Span,
 }
 
 #[async_trait]
 impl ManagedPlugin for BlockStorage {
     fn new(_: &mut Plugins) -> Arc<Self> {
-        Arc::new(Self {
-            new_address: Default::default(),
-            blocks: Default::default(),
-            span: info_span!("block_storage"),
-        })
+        Self::new_with_store(None)
     }
 
     async fn start(&self) {
         debug!("issuing genesis block");
-        self.insert(GenesisBlock(Id::default()));
+        let _ = self.insert_block(GenesisBlock(Id::default()), false);
+
+        if let Some(store) = &self.store {
+            match store.blocks() {
+                Ok(blocks) => {
+                    debug!(count = blocks.len(), "replaying stored blocks");
+                    for block in blocks {
+                        let _ = self.insert_block(block, false);
+                    }
+                }
+                Err(err) => warn!(%err, "failed to load stored blocks"),
+            }
+        }
     }
 
     async fn shutdown(&self) {
@@ -45,27 +63,98 @@ impl ManagedPlugin for BlockStorage {
 }
 
 impl BlockStorage {
-    pub fn insert(&self, block: Block) -> BlockMetadata {
-        self.address(block.id())
-            .get_or_insert_with(|| {
-                trace!("new block metadata stored");
-                BlockMetadata::new(block)
-            })
-            .clone()
-            .unwrap()
+    /// Creates a storage that persists inserted blocks in the given store and replays them on
+    /// start.
+    pub fn with_store(store: Arc<dyn BlockStore>) -> Arc<Self> {
+        Self::new_with_store(Some(store))
+    }
+
+    fn new_with_store(store: Option<Arc<dyn BlockStore>>) -> Arc<Self> {
+        Arc::new(Self {
+            new_address: Default::default(),
+            blocks: Default::default(),
+            pruned: Default::default(),
+            inserting: Default::default(),
+            store,
+            span: info_span!("block_storage"),
+        })
+    }
+
+    /// Stores the block and returns its metadata, or `None` if the block was already stored or
+    /// has been pruned.
+    pub fn insert(&self, block: Block) -> Option<BlockMetadata> {
+        match self.insert_block(block, true) {
+            Ok((metadata, true)) => Some(metadata),
+            Ok((_, false)) => None,
+            Err(err) => {
+                debug!(%err, "block not stored");
+                None
+            }
+        }
+    }
+
+    /// Returns the store that persists the blocks, if any.
+    pub fn store(&self) -> Option<&Arc<dyn BlockStore>> {
+        self.store.as_ref()
     }
 
     pub fn get(&self, block_id: &BlockID) -> Option<BlockMetadata> {
-        let addresses = self.blocks.lock().unwrap();
-        addresses
-            .get(block_id)
-            .and_then(|a| a.get().as_ref().cloned())
+        let address = self.blocks.lock().unwrap().get(block_id).cloned();
+        address.and_then(|a| a.get().as_ref().cloned())
+    }
+
+    /// Removes a block from the storage. Its metadata is dropped as soon as the last strong
+    /// reference to it is gone, after which weak references to it fail to resolve. The block is
+    /// remembered as pruned, so its address can not be allocated again.
+    pub fn evict(&self, block_id: &BlockID) -> Option<BlockMetadata> {
+        let mut pruned = self.pruned.lock().unwrap();
+        let address = self.blocks.lock().unwrap().remove(block_id)?;
+        pruned.insert(block_id.clone());
+
+        address.get().as_ref().cloned()
+    }
+
+    /// Returns true if the block was evicted from the storage.
+    pub fn is_pruned(&self, block_id: &BlockID) -> bool {
+        self.pruned.lock().unwrap().contains(block_id)
+    }
+
+    /// Returns all blocks whose metadata is currently stored.
+    pub fn blocks(&self) -> Vec<BlockMetadata> {
+        self.addresses()
+            .into_iter()
+            .filter_map(|(_, a)| a.get().as_ref().cloned())
+            .collect()
+    }
+
+    /// Returns the ids of blocks whose address has been allocated (e.g. because they are
+    /// referenced as a parent) but that have not been stored yet.
+    pub fn missing_blocks(&self) -> Vec<BlockID> {
+        self.addresses()
+            .into_iter()
+            .filter(|(_, a)| a.get().is_none())
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    pub fn size(&self) -> usize {
+        self.blocks.lock().unwrap().len()
     }
 
-    pub fn address(&self, block_id: &BlockID) -> Address {
+    /// Returns the address of the block, allocating it if it is not known yet. Fails for blocks
+    /// that have been pruned.
+    pub fn address(&self, block_id: &BlockID) -> Result<Address> {
         let mut is_new = false;
 
         let address = {
+            let pruned = self.pruned.lock().unwrap();
+            if pruned.contains(block_id) {
+                return Err(Error::BlockPruned {
+                    block_id: block_id.clone(),
+                    backtrace: Backtrace::capture(),
+                });
+            }
+
             let mut blocks = self.blocks.lock().unwrap();
             blocks
                 .entry(block_id.clone())
@@ -78,9 +167,45 @@ impl BlockStorage {
         };
 
         if is_new {
-            self.new_address.trigger(&address);
+            self.new_address
+                .trigger(&(block_id.clone(), address.clone()));
+        }
+
+        Ok(address)
+    }
+
+    /// Returns a snapshot of the allocated addresses. Addresses must not be read while holding the
+    /// lock, as filling an address triggers callbacks that allocate new ones.
+    fn addresses(&self) -> Vec<(BlockID, Address)> {
+        let blocks = self.blocks.lock().unwrap();
+        blocks
+            .iter()
+            .map(|(id, a)| (id.clone(), a.clone()))
+            .collect()
+    }
+
+    /// Stores the block unless it is already stored. Returns its metadata and whether it was
+    /// inserted by this call.
+    ///
+    /// The block is persisted before its address is filled, so that it is stored before any
+    /// metadata derived from it, but without holding the lock of the address while committing.
+    fn insert_block(&self, block: Block, persist: bool) -> Result<(BlockMetadata, bool)> {
+        let _inserting = self.inserting.lock().unwrap();
+        let address = self.address(block.id())?;
+        if let Some(metadata) = address.get().as_ref() {
+            return Ok((metadata.clone(), false));
         }
 
-        address
+        if let Some(store) = self.store.as_ref().filter(|_| persist)
+            && let Err(err) = store.insert(&block)
+        {
+            warn!(block_id = %block.id(), %err, "failed to persist block");
+        }
+
+        trace!("new block metadata stored");
+        let metadata = BlockMetadata::new(block);
+        address.set(metadata.clone());
+
+        Ok((metadata, true))
     }
 }
@@ -0,0 +1,23 @@
+use common::{blocks::Block, errors::Result, ids::BlockID};
+
+/// A backend that persists the raw blocks of the [`BlockStorage`](crate::BlockStorage) so that
+/// they can be replayed after a restart.
+///
+/// Blocks are returned in the order they were inserted. Replaying them in that order feeds the
+/// DAG the same sequence of blocks it saw originally, which is enough to rebuild all derived
+/// metadata (votes, acceptance) deterministically.
+///
+/// Evicting a block from the [`BlockStorage`](crate::BlockStorage) only releases its memory. The
+/// store keeps the full history so that a restarted node can still replay it.
+pub trait BlockStore: Send + Sync {
+    fn insert(&self, block: &Block) -> Result<()>;
+
+    fn blocks(&self) -> Result<Vec<Block>>;
+
+    /// Records the milestones accepted above `height`, the first one at `height + 1`. Milestones
+    /// recorded above `height` before are replaced, as they were reverted by a reorg.
+    fn accept_milestones(&self, height: u64, milestones: &[BlockID]) -> Result<()>;
+
+    /// Returns the accepted milestones ordered by height.
+    fn accepted_milestones(&self) -> Result<Vec<(u64, BlockID)>>;
+}
@@ -0,0 +1,121 @@
+use std::{backtrace::Backtrace, path::Path};
+
+use common::{
+    blocks::Block,
+    errors::{Error, Result},
+    ids::BlockID,
+};
+use redb::{Database, ReadableTable, TableDefinition};
+
+use crate::BlockStore;
+
+/// Encoded blocks keyed by their insertion index.
+const BLOCKS: TableDefinition<u64, &[u8]> = TableDefinition::new("blocks");
+
+/// Insertion indexes keyed by block ID, used to skip blocks that are already stored.
+const INDEXES: TableDefinition<&[u8], u64> = TableDefinition::new("indexes");
+
+/// Accepted milestone IDs keyed by their height.
+const MILESTONES: TableDefinition<u64, &[u8]> = TableDefinition::new("milestones");
+
+/// A [`BlockStore`] backed by an embedded on-disk key-value store.
+pub struct DiskBlockStore {
+    database: Database,
+}
+
+impl DiskBlockStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let database = Database::create(path).map_err(storage_failed)?;
+
+        let write = database.begin_write().map_err(storage_failed)?;
+        write.open_table(BLOCKS).map_err(storage_failed)?;
+        write.open_table(INDEXES).map_err(storage_failed)?;
+        write.open_table(MILESTONES).map_err(storage_failed)?;
+        write.commit().map_err(storage_failed)?;
+
+        Ok(Self { database })
+    }
+}
+
+impl BlockStore for DiskBlockStore {
+    fn insert(&self, block: &Block) -> Result<()> {
+        // write transactions are exclusive, so the next index can be derived from the last block
+        let write = self.database.begin_write().map_err(storage_failed)?;
+        {
+            let mut indexes = write.open_table(INDEXES).map_err(storage_failed)?;
+            if indexes
+                .get(block.id().as_slice())
+                .map_err(storage_failed)?
+                .is_some()
+            {
+                return Ok(());
+            }
+
+            let mut blocks = write.open_table(BLOCKS).map_err(storage_failed)?;
+            let index = match blocks.last().map_err(storage_failed)? {
+                Some((index, _)) => index.value() + 1,
+                None => 0,
+            };
+            blocks
+                .insert(index, block.to_bytes().as_slice())
+                .map_err(storage_failed)?;
+            indexes
+                .insert(block.id().as_slice(), index)
+                .map_err(storage_failed)?;
+        }
+        write.commit().map_err(storage_failed)
+    }
+
+    fn blocks(&self) -> Result<Vec<Block>> {
+        let read = self.database.begin_read().map_err(storage_failed)?;
+        let blocks = read.open_table(BLOCKS).map_err(storage_failed)?;
+
+        let mut result = Vec::new();
+        for entry in blocks.iter().map_err(storage_failed)? {
+            let (_, bytes) = entry.map_err(storage_failed)?;
+            result.push(Block::from_bytes(bytes.value())?);
+        }
+
+        Ok(result)
+    }
+
+    fn accept_milestones(&self, height: u64, milestones: &[BlockID]) -> Result<()> {
+        let write = self.database.begin_write().map_err(storage_failed)?;
+        {
+            let mut accepted = write.open_table(MILESTONES).map_err(storage_failed)?;
+            accepted
+                .retain_in(height + 1.., |_, _| false)
+                .map_err(storage_failed)?;
+            for (height, milestone) in (height + 1..).zip(milestones) {
+                accepted
+                    .insert(height, milestone.as_slice())
+                    .map_err(storage_failed)?;
+            }
+        }
+        write.commit().map_err(storage_failed)
+    }
+
+    fn accepted_milestones(&self) -> Result<Vec<(u64, BlockID)>> {
+        let read = self.database.begin_read().map_err(storage_failed)?;
+        let accepted = read.open_table(MILESTONES).map_err(storage_failed)?;
+
+        let mut result = Vec::new();
+        for entry in accepted.iter().map_err(storage_failed)? {
+            let (height, id) = entry.map_err(storage_failed)?;
+            let id = <[u8; 32]>::try_from(id.value()).map_err(|_| Error::StorageFailed {
+                reason: format!("invalid milestone id at height {}", height.value()),
+                backtrace: Backtrace::capture(),
+            })?;
+            result.push((height.value(), BlockID::from(id)));
+        }
+
+        Ok(result)
+    }
+}
+
+fn storage_failed(err: impl Into<redb::Error>) -> Error {
+    Error::StorageFailed {
+        reason: err.into().to_string(),
+        backtrace: Backtrace::capture(),
+    }
+}
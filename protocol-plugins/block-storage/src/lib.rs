@@ -1,4 +1,9 @@
 mod address;
 mod block_storage;
+mod block_store;
+mod disk_block_store;
+mod memory_block_store;
 
-pub use crate::{address::*, block_storage::*};
+pub use crate::{
+    address::*, block_storage::*, block_store::*, disk_block_store::*, memory_block_store::*,
+};
@@ -0,0 +1,40 @@
+use std::{collections::BTreeMap, sync::Mutex};
+
+use common::{blocks::Block, errors::Result, ids::BlockID};
+use indexmap::IndexMap;
+
+use crate::BlockStore;
+
+/// A [`BlockStore`] that keeps blocks in memory. It survives restarts of a protocol instance but
+/// not of the process.
+#[derive(Default)]
+pub struct MemoryBlockStore {
+    blocks: Mutex<IndexMap<BlockID, Block>>,
+    milestones: Mutex<BTreeMap<u64, BlockID>>,
+}
+
+impl BlockStore for MemoryBlockStore {
+    fn insert(&self, block: &Block) -> Result<()> {
+        self.blocks
+            .lock()
+            .unwrap()
+            .insert(block.id().clone(), block.clone());
+        Ok(())
+    }
+
+    fn blocks(&self) -> Result<Vec<Block>> {
+        Ok(self.blocks.lock().unwrap().values().cloned().collect())
+    }
+
+    fn accept_milestones(&self, height: u64, milestones: &[BlockID]) -> Result<()> {
+        let mut accepted = self.milestones.lock().unwrap();
+        accepted.split_off(&(height + 1));
+        accepted.extend((height + 1..).zip(milestones.iter().cloned()));
+        Ok(())
+    }
+
+    fn accepted_milestones(&self) -> Result<Vec<(u64, BlockID)>> {
+        let accepted = self.milestones.lock().unwrap();
+        Ok(accepted.iter().map(|(h, id)| (*h, id.clone())).collect())
+    }
+}
@@ -0,0 +1,52 @@
+use block_storage::{BlockStore, DiskBlockStore};
+use common::{
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::BlockID,
+};
+
+fn block(payload: &[u8]) -> Block {
+    Block::from(NetworkBlock::new(
+        vec![BlockID::default()],
+        1,
+        payload.to_vec(),
+        &SigningKey::from([1; 32]),
+    ))
+}
+
+#[test]
+fn test_disk_block_store() {
+    let path = std::env::temp_dir().join(format!("disk-block-store-{}.redb", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let (a, b, c) = (block(b"a"), block(b"b"), block(b"c"));
+    {
+        let store = DiskBlockStore::open(&path).unwrap();
+        store.insert(&a).unwrap();
+        store.insert(&b).unwrap();
+        store.insert(&a).unwrap();
+        store.insert(&c).unwrap();
+
+        store
+            .accept_milestones(0, &[a.id().clone(), b.id().clone()])
+            .unwrap();
+        // a reorg at height 1 replaces the milestone at height 2
+        store.accept_milestones(1, &[c.id().clone()]).unwrap();
+    }
+
+    let store = DiskBlockStore::open(&path).unwrap();
+    let ids: Vec<_> = store
+        .blocks()
+        .unwrap()
+        .iter()
+        .map(|block| block.id().clone())
+        .collect();
+    assert_eq!(ids, vec![a.id().clone(), b.id().clone(), c.id().clone()]);
+    assert_eq!(
+        store.accepted_milestones().unwrap(),
+        vec![(1, a.id().clone()), (2, c.id().clone())]
+    );
+
+    drop(store);
+    let _ = std::fs::remove_file(&path);
+}
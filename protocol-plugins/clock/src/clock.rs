@@ -0,0 +1,12 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+/// Source of the current time in milliseconds since the UNIX epoch.
+#[async_trait]
+pub trait Clock: Send + Sync {
+    fn now(&self) -> u64;
+
+    /// Waits until the given duration passed on this clock.
+    async fn sleep(&self, duration: Duration);
+}
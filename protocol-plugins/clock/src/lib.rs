@@ -0,0 +1,6 @@
+mod clock;
+mod mock_clock;
+mod protocol_clock;
+mod system_clock;
+
+pub use crate::{clock::*, mock_clock::*, protocol_clock::*, system_clock::*};
@@ -0,0 +1,57 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+
+use crate::Clock;
+
+/// Clock that only moves when it is told to, used to make time dependent logic deterministic.
+///
+/// Sleeping on it only completes once the clock was moved far enough.
+#[derive(Default)]
+pub struct MockClock {
+    now: AtomicU64,
+    moved: Notify,
+}
+
+impl MockClock {
+    pub fn new(now: u64) -> Self {
+        Self {
+            now: AtomicU64::new(now),
+            moved: Notify::new(),
+        }
+    }
+
+    pub fn set(&self, now: u64) {
+        self.now.store(now, Ordering::SeqCst);
+        self.moved.notify_waiters();
+    }
+
+    pub fn advance(&self, duration: u64) -> u64 {
+        let now = self.now.fetch_add(duration, Ordering::SeqCst) + duration;
+        self.moved.notify_waiters();
+        now
+    }
+}
+
+#[async_trait]
+impl Clock for MockClock {
+    fn now(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        let deadline = self.now().saturating_add(duration.as_millis() as u64);
+        loop {
+            // register for the notification before checking, so a concurrent move is not missed
+            let moved = self.moved.notified();
+            if self.now() >= deadline {
+                return;
+            }
+            moved.await;
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::{sync::Arc, time::Duration};
+
+use protocol::{ManagedPlugin, Plugins};
+use tracing::{Span, info_span};
+
+use crate::{Clock, SystemClock};
+
+/// Plugin that gives the protocol access to the current time.
+///
+/// It uses the [`SystemClock`] unless a different [`Clock`] is provided before the first plugin
+/// loads it:
+///
+/// ```ignore
+/// plugins.provide(Arc::new(ProtocolClock::with_clock(Arc::new(MockClock::new(0)))));
+/// ```
+pub struct ProtocolClock {
+    clock: Arc<dyn Clock>,
+    span: Span,
+}
+
+impl ProtocolClock {
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            clock,
+            span: info_span!("clock"),
+        }
+    }
+
+    pub fn now(&self) -> u64 {
+        self.clock.now()
+    }
+
+    /// Waits until the given duration passed on the clock.
+    pub async fn sleep(&self, duration: Duration) {
+        self.clock.sleep(duration).await
+    }
+}
+
+impl ManagedPlugin for ProtocolClock {
+    fn new(_: &mut Plugins) -> Arc<Self> {
+        Arc::new(Self::with_clock(Arc::new(SystemClock)))
+    }
+
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
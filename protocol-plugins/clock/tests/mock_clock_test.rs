@@ -0,0 +1,43 @@
+use std::{sync::Arc, time::Duration};
+
+use clock::{Clock, MockClock, ProtocolClock};
+
+#[test]
+fn test_mock_clock() {
+    let clock = MockClock::new(1_000);
+    assert_eq!(clock.now(), 1_000);
+
+    assert_eq!(clock.advance(250), 1_250);
+    assert_eq!(clock.now(), 1_250);
+
+    clock.set(42);
+    assert_eq!(clock.now(), 42);
+}
+
+#[test]
+fn test_protocol_clock() {
+    let clock = Arc::new(MockClock::new(7));
+    let protocol_clock = ProtocolClock::with_clock(clock.clone());
+    assert_eq!(protocol_clock.now(), 7);
+
+    clock.advance(3);
+    assert_eq!(protocol_clock.now(), 10);
+}
+
+#[tokio::test]
+async fn test_mock_clock_sleep() {
+    let clock = Arc::new(MockClock::new(0));
+    let sleep = tokio::spawn({
+        let clock = clock.clone();
+        async move { clock.sleep(Duration::from_millis(100)).await }
+    });
+
+    // the sleep only completes once the clock moved far enough
+    tokio::task::yield_now().await;
+    clock.advance(99);
+    tokio::task::yield_now().await;
+    assert!(!sleep.is_finished());
+
+    clock.advance(1);
+    sleep.await.unwrap();
+}
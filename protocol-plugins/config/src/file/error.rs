@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+/// The errors of loading a [`Config`](crate::Config) from a file.
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("failed to read config file `{}`: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("unsupported config file `{}` (expected a .toml or .json file)", path.display())]
+    UnsupportedFormat { path: PathBuf },
+
+    #[error("malformed config file: {reason}")]
+    Syntax { reason: String },
+
+    #[error("invalid value for `{key}`: {reason}")]
+    InvalidValue { key: String, reason: String },
+}
+
+impl ConfigError {
+    pub(crate) fn invalid_value(key: impl Into<String>, reason: impl ToString) -> Self {
+        Self::InvalidValue {
+            key: key.into(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Converts a deserialization error, prefixing its path with the given section (if any).
+    pub(crate) fn from_path_error<E: ToString>(
+        section: Option<&str>,
+        error: serde_path_to_error::Error<E>,
+    ) -> Self {
+        let path = error.path().to_string();
+        let key = match (section, path.as_str()) {
+            (Some(section), ".") => section.to_string(),
+            (Some(section), path) => format!("{section}.{path}"),
+            (None, path) => path.to_string(),
+        };
+
+        Self::invalid_value(key, error.into_inner())
+    }
+}
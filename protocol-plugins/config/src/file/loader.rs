@@ -0,0 +1,262 @@
+use std::{collections::HashMap, path::Path};
+
+use common::{
+    bft::{Committee, Member},
+    codec::deserialize_hex,
+    ids::IssuerID,
+};
+use inbox::InboxConfigParams;
+use networking::NetworkingConfigParams;
+use protocol::ProtocolConfig;
+use pruning::PruningConfigParams;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use solidifier::SolidifierConfigParams;
+use validator::ValidatorConfigParams;
+
+use crate::{
+    CommitteeSelection, Config, ConfigError, ConfigSection, ProtocolParams, ProtocolPlugins,
+    SlotDuration, SlotSchedule,
+};
+
+/// Loads a [`Config`] from a TOML or JSON file.
+///
+/// The top level of a file holds the protocol settings, while the params of the plugins live in
+/// their own sections (tables in TOML), keyed by [`ConfigSection::NAME`]:
+///
+/// ```toml
+/// genesis_time = 1_700_000_000_000
+/// slot_duration = 1_000
+/// offline_threshold = 3
+/// plugins = "validator"
+///
+/// [[committee]]
+/// id = "0x0101010101010101010101010101010101010101010101010101010101010101"
+/// weight = 2
+///
+/// [inbox]
+/// capacity = 5_000
+///
+/// [validator]
+/// seed = "0x0101010101010101010101010101010101010101010101010101010101010101"
+/// ```
+///
+/// Settings that are missing keep their defaults. Unknown keys are rejected, so sections of
+/// plugins that live outside of this crate have to be registered with [`Self::with_section`].
+pub struct ConfigLoader {
+    sections: HashMap<&'static str, SectionLoader>,
+    plugins: HashMap<String, ProtocolPlugins>,
+}
+
+impl ConfigLoader {
+    /// Registers the params section of a plugin.
+    pub fn with_section<T: ConfigSection>(mut self) -> Self {
+        self.sections.insert(T::NAME, load_section::<T>);
+        self
+    }
+
+    /// Registers a set of plugins that files can select by name, next to the presets `core`,
+    /// `observer`, `validator` and `archive`.
+    pub fn with_plugins(mut self, name: &str, plugins: ProtocolPlugins) -> Self {
+        self.plugins.insert(name.to_string(), plugins);
+        self
+    }
+
+    /// Loads the file at the given path, choosing the format by its extension.
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<Config, ConfigError> {
+        let path = path.as_ref();
+        let read = || {
+            std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        };
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => self.load_toml(&read()?),
+            Some("json") => self.load_json(&read()?),
+            _ => Err(ConfigError::UnsupportedFormat {
+                path: path.to_path_buf(),
+            }),
+        }
+    }
+
+    pub fn load_toml(&self, content: &str) -> Result<Config, ConfigError> {
+        let value = toml::from_str(content).map_err(|error| ConfigError::Syntax {
+            reason: error.message().to_string(),
+        })?;
+
+        self.load_value(value)
+    }
+
+    pub fn load_json(&self, content: &str) -> Result<Config, ConfigError> {
+        let value = serde_json::from_str(content).map_err(|error| ConfigError::Syntax {
+            reason: error.to_string(),
+        })?;
+
+        self.load_value(value)
+    }
+
+    fn load_value(&self, value: Value) -> Result<Config, ConfigError> {
+        let Value::Object(mut settings) = value else {
+            return Err(ConfigError::Syntax {
+                reason: "expected a table of settings".to_string(),
+            });
+        };
+
+        // sections are taken out first, so that the remaining keys are the protocol settings
+        let mut sections = Vec::new();
+        for (name, load) in &self.sections {
+            if let Some(section) = settings.remove(*name) {
+                sections.push((*load, section));
+            }
+        }
+
+        let mut config = self.protocol_settings(settings)?;
+        for (load, section) in sections {
+            config = load(config, section)?;
+        }
+
+        Ok(config)
+    }
+
+    fn protocol_settings(&self, settings: Map<String, Value>) -> Result<Config, ConfigError> {
+        let settings: ProtocolSettings = serde_path_to_error::deserialize(Value::Object(settings))
+            .map_err(|error| ConfigError::from_path_error(None, error))?;
+
+        let plugins = match &settings.plugins {
+            Some(name) => self.plugins.get(name).cloned().ok_or_else(|| {
+                ConfigError::invalid_value("plugins", format!("unknown plugins `{name}`"))
+            })?,
+            None => ProtocolPlugins::default(),
+        };
+        let mut config =
+            Config::default().with_protocol_params(ProtocolParams::default().with_plugins(plugins));
+
+        if let Some(genesis_time) = settings.genesis_time {
+            config = config.with_genesis_time(genesis_time);
+        }
+        if let Some(slot_duration) = settings.slot_duration {
+            config = config.with_slot_duration(slot_duration.into());
+        }
+        if let Some(members) = settings.committee {
+            if members.is_empty() {
+                return Err(ConfigError::invalid_value(
+                    "committee",
+                    "expected at least one member",
+                ));
+            }
+
+            config = config.with_committee_selection(CommitteeSelection::FixedCommittee(
+                Committee::from(
+                    members
+                        .into_iter()
+                        .map(|member| Member::new(member.id).with_weight(member.weight)),
+                ),
+            ));
+        }
+        if let Some(offline_threshold) = settings.offline_threshold {
+            config = config.with_offline_threshold(offline_threshold);
+        }
+        if let Some(max_time_drift) = settings.max_time_drift {
+            config = config.with_max_time_drift(max_time_drift);
+        }
+
+        Ok(config)
+    }
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        Self {
+            sections: HashMap::new(),
+            plugins: HashMap::new(),
+        }
+        .with_section::<InboxConfigParams>()
+        .with_section::<NetworkingConfigParams>()
+        .with_section::<PruningConfigParams>()
+        .with_section::<SolidifierConfigParams>()
+        .with_section::<ValidatorConfigParams>()
+        .with_plugins("core", ProtocolPlugins::core())
+        .with_plugins("observer", ProtocolPlugins::observer())
+        .with_plugins("validator", ProtocolPlugins::validator())
+        .with_plugins("archive", ProtocolPlugins::archive())
+    }
+}
+
+impl Config {
+    /// Loads the config from a TOML or JSON file (see [`ConfigLoader`]).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        ConfigLoader::default().load(path)
+    }
+}
+
+type SectionLoader = fn(Config, Value) -> Result<Config, ConfigError>;
+
+fn load_section<T: ConfigSection>(config: Config, section: Value) -> Result<Config, ConfigError> {
+    let params: T = serde_path_to_error::deserialize(section)
+        .map_err(|error| ConfigError::from_path_error(Some(T::NAME), error))?;
+
+    Ok(config.with_params(params))
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProtocolSettings {
+    genesis_time: Option<u64>,
+    slot_duration: Option<SlotDurationSettings>,
+    committee: Option<Vec<MemberSettings>>,
+    offline_threshold: Option<u64>,
+    max_time_drift: Option<u64>,
+    plugins: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged, expecting = "a duration in milliseconds or a slot schedule")]
+enum SlotDurationSettings {
+    Static(u64),
+    Scheduled(SlotScheduleSettings),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SlotScheduleSettings {
+    duration: u64,
+    epoch_slots: u64,
+    #[serde(default)]
+    changes: Vec<SlotDurationChange>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SlotDurationChange {
+    epoch: u64,
+    duration: u64,
+}
+
+impl From<SlotDurationSettings> for SlotDuration {
+    fn from(settings: SlotDurationSettings) -> Self {
+        match settings {
+            SlotDurationSettings::Static(duration) => SlotDuration::Static(duration),
+            SlotDurationSettings::Scheduled(schedule) => {
+                SlotDuration::Scheduled(schedule.changes.into_iter().fold(
+                    SlotSchedule::new(schedule.duration, schedule.epoch_slots),
+                    |schedule, change| schedule.with_duration_from(change.epoch, change.duration),
+                ))
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MemberSettings {
+    #[serde(deserialize_with = "deserialize_hex")]
+    id: IssuerID,
+    #[serde(default = "default_weight")]
+    weight: u64,
+}
+
+fn default_weight() -> u64 {
+    1
+}
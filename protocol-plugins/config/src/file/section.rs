@@ -0,0 +1,38 @@
+use std::any::Any;
+
+use inbox::InboxConfigParams;
+use networking::NetworkingConfigParams;
+use pruning::PruningConfigParams;
+use serde::de::DeserializeOwned;
+use solidifier::SolidifierConfigParams;
+use validator::ValidatorConfigParams;
+
+/// The params of a plugin that can be loaded from their own section of a config file.
+///
+/// Sections are registered with [`ConfigLoader::with_section`](crate::ConfigLoader::with_section)
+/// and end up in the config as if they were added with
+/// [`ProtocolConfig::with_params`](protocol::ProtocolConfig::with_params).
+pub trait ConfigSection: DeserializeOwned + Any + Send + Sync {
+    /// The key of the section in the config file.
+    const NAME: &'static str;
+}
+
+impl ConfigSection for InboxConfigParams {
+    const NAME: &'static str = "inbox";
+}
+
+impl ConfigSection for NetworkingConfigParams {
+    const NAME: &'static str = "networking";
+}
+
+impl ConfigSection for PruningConfigParams {
+    const NAME: &'static str = "pruning";
+}
+
+impl ConfigSection for SolidifierConfigParams {
+    const NAME: &'static str = "solidifier";
+}
+
+impl ConfigSection for ValidatorConfigParams {
+    const NAME: &'static str = "validator";
+}
@@ -0,0 +1,34 @@
+use inbox::{InboxConfig, InboxConfigParams};
+use protocol::ProtocolConfig;
+
+use crate::Config;
+
+impl InboxConfig for Config {
+    fn inbox_capacity(&self) -> usize {
+        self.params::<InboxConfigParams>()
+            .map_or(InboxConfigParams::default().capacity, |params| {
+                params.capacity
+            })
+    }
+
+    fn inbox_peer_rate(&self) -> u32 {
+        self.params::<InboxConfigParams>()
+            .map_or(InboxConfigParams::default().peer_rate, |params| {
+                params.peer_rate
+            })
+    }
+
+    fn inbox_peer_burst(&self) -> u32 {
+        self.params::<InboxConfigParams>()
+            .map_or(InboxConfigParams::default().peer_burst, |params| {
+                params.peer_burst
+            })
+    }
+
+    fn inbox_max_parents(&self) -> usize {
+        self.params::<InboxConfigParams>()
+            .map_or(InboxConfigParams::default().max_parents, |params| {
+                params.max_parents
+            })
+    }
+}
@@ -1,5 +1,35 @@
 mod config;
 
+mod file {
+    mod error;
+    mod loader;
+    mod section;
+
+    pub use error::*;
+    pub use loader::*;
+    pub use section::*;
+}
+
+mod inbox {
+    mod params;
+}
+
+mod networking {
+    mod params;
+}
+
+mod pruning {
+    mod params;
+}
+
+mod solidifier {
+    mod params;
+}
+
+mod stake_registry {
+    mod params;
+}
+
 mod protocol {
     mod params;
     mod plugins;
@@ -9,17 +39,24 @@ mod protocol {
 }
 
 mod virtual_voting {
+    mod committee_rotation;
     mod committee_selection;
     mod leader_rotation;
     mod params;
     mod slot_duration;
 
+    pub use committee_rotation::*;
     pub use committee_selection::*;
     pub use leader_rotation::*;
     pub use params::*;
     pub use slot_duration::*;
 }
 
+mod validator {
+    mod params;
+}
+
 pub use config::*;
+pub use file::*;
 pub use protocol::*;
 pub use virtual_voting::*;
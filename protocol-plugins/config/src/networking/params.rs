@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use networking::{NetworkingConfig, NetworkingConfigParams};
+use protocol::ProtocolConfig;
+
+use crate::Config;
+
+impl NetworkingConfig for Config {
+    fn gossip_fanout(&self) -> usize {
+        self.params::<NetworkingConfigParams>()
+            .map_or(NetworkingConfigParams::default().gossip_fanout, |params| {
+                params.gossip_fanout
+            })
+    }
+
+    fn gossip_seen_capacity(&self) -> usize {
+        self.params::<NetworkingConfigParams>().map_or(
+            NetworkingConfigParams::default().gossip_seen_capacity,
+            |params| params.gossip_seen_capacity,
+        )
+    }
+
+    fn gossip_request_timeout(&self) -> Duration {
+        Duration::from_millis(self.params::<NetworkingConfigParams>().map_or(
+            NetworkingConfigParams::default().gossip_request_timeout,
+            |params| params.gossip_request_timeout,
+        ))
+    }
+}
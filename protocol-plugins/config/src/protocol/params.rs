@@ -1,7 +1,10 @@
 use std::{any::Any, sync::Arc};
 
+use block_storage::{BlockStorage, BlockStore};
+use clock::{Clock, ProtocolClock};
 use common::collections::AnyMap;
 use protocol::Plugins;
+use snapshot::{Snapshot, SnapshotBlockStore};
 
 use crate::{Config, ProtocolPlugins};
 
@@ -9,6 +12,9 @@ use crate::{Config, ProtocolPlugins};
 pub struct ProtocolParams {
     params: AnyMap,
     plugins: ProtocolPlugins,
+    clock: Option<Arc<dyn Clock>>,
+    block_store: Option<Arc<dyn BlockStore>>,
+    snapshot: Option<Arc<Snapshot>>,
 }
 
 impl ProtocolParams {
@@ -16,6 +22,27 @@ impl ProtocolParams {
         self.plugins = plugins;
         self
     }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    pub fn with_block_store(mut self, block_store: Arc<dyn BlockStore>) -> Self {
+        self.block_store = Some(block_store);
+        self
+    }
+
+    /// Bootstraps from the given snapshot instead of from genesis. Blocks of the block store (if
+    /// any) are replayed on top of the snapshot.
+    pub fn with_snapshot(mut self, snapshot: Snapshot) -> Self {
+        self.snapshot = Some(Arc::new(snapshot));
+        self
+    }
+
+    pub fn snapshot(&self) -> Option<&Arc<Snapshot>> {
+        self.snapshot.as_ref()
+    }
 }
 
 impl protocol::ProtocolConfig for Config {
@@ -29,6 +56,26 @@ impl protocol::ProtocolConfig for Config {
     }
 
     fn inject_plugins(&self, mut registry: Plugins) -> Plugins {
+        if let Some(clock) = &self.protocol_params.clock {
+            registry.provide(Arc::new(ProtocolClock::with_clock(clock.clone())));
+        }
+
+        match (
+            &self.protocol_params.snapshot,
+            &self.protocol_params.block_store,
+        ) {
+            (Some(snapshot), block_store) => {
+                registry.provide(BlockStorage::with_store(Arc::new(SnapshotBlockStore::new(
+                    snapshot.clone(),
+                    block_store.clone(),
+                ))));
+            }
+            (None, Some(block_store)) => {
+                registry.provide(BlockStorage::with_store(block_store.clone()));
+            }
+            (None, None) => {}
+        }
+
         self.protocol_params.plugins.inject(self, &mut registry);
         registry
     }
@@ -1,46 +1,170 @@
+use std::any::{Any, TypeId};
+
 use block_dag::BlockDAG;
 use block_factory::BlockFactory;
 use block_storage::BlockStorage;
-use consensus::Consensus;
+use consensus::{AcceptanceRecorder, Consensus};
 use consensus_feed::ConsensusFeed;
 use consensus_round::ConsensusRound;
 use inbox::Inbox;
 use networking::Networking;
 use outbox::Outbox;
-use protocol::Plugins;
+use protocol::{ManagedPlugin, Plugins};
+use pruning::Pruning;
+use solidifier::Solidifier;
+use stake_registry::StakeTracker;
 use tip_selection::TipSelection;
+use validator::Validator;
 use virtual_voting::VirtualVoting;
 
 use crate::Config;
 
-pub enum ProtocolPlugins {
-    Core,
-    Custom(fn(&Config, &mut Plugins)),
+/// The plugins that are loaded into a protocol instance (in the order that they start in).
+///
+/// Sets start from one of the presets and add, remove or replace individual plugins by type:
+///
+/// ```ignore
+/// ProtocolPlugins::validator()
+///     .without::<Networking<Config>>()
+///     .replace_with::<TipSelection<Config>>(|_, registry| {
+///         registry.provide(custom_tip_selection());
+///     });
+/// ```
+///
+/// Plugins load the plugins they depend on themselves, so removing a plugin from the set only
+/// keeps it from being loaded if no other plugin of the set depends on it.
+#[derive(Clone)]
+pub struct ProtocolPlugins {
+    entries: Vec<PluginEntry>,
 }
 
 impl ProtocolPlugins {
+    /// The plugins that every node runs to follow the consensus and to issue blocks.
+    pub fn core() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+        .with::<BlockStorage>()
+        .with::<BlockDAG>()
+        .with::<VirtualVoting<Config>>()
+        .with::<TipSelection<Config>>()
+        .with::<Outbox>()
+        .with::<Inbox<Config>>()
+        .with::<Networking<Config>>()
+        .with::<Consensus<Config>>()
+        .with::<AcceptanceRecorder<Config>>()
+        .with::<ConsensusRound<Config>>()
+        .with::<BlockFactory<Config>>()
+        .with::<ConsensusFeed<Config>>()
+        .with::<StakeTracker<Config>>()
+    }
+
+    /// Follows the consensus without issuing blocks, requesting missing blocks from its peers and
+    /// pruning the accepted history.
+    pub fn observer() -> Self {
+        Self::core()
+            .without::<ConsensusRound<Config>>()
+            .without::<BlockFactory<Config>>()
+            .with::<Pruning<Config>>()
+            .with::<Solidifier<Config>>()
+    }
+
+    /// Follows the consensus and issues blocks with the key of the
+    /// [`ValidatorConfigParams`](validator::ValidatorConfigParams).
+    pub fn validator() -> Self {
+        Self::core()
+            .with::<Validator<Config>>()
+            .with::<Pruning<Config>>()
+            .with::<Solidifier<Config>>()
+    }
+
+    /// An observer that keeps the whole history.
+    pub fn archive() -> Self {
+        Self::observer().without::<Pruning<Config>>()
+    }
+
+    /// Adds the plugin to the end of the set (unless it is already part of it).
+    pub fn with<T: ManagedPlugin + Any>(mut self) -> Self {
+        if !self.contains::<T>() {
+            self.entries.push(PluginEntry::of::<T>(load::<T>));
+        }
+        self
+    }
+
+    /// Adds a loader to the end of the set that loads or provides arbitrary plugins.
+    pub fn with_loader(mut self, inject: fn(&Config, &mut Plugins)) -> Self {
+        self.entries.push(PluginEntry {
+            type_id: None,
+            inject,
+        });
+        self
+    }
+
+    pub fn without<T: Any>(mut self) -> Self {
+        self.entries
+            .retain(|entry| entry.type_id != Some(TypeId::of::<T>()));
+        self
+    }
+
+    /// Loads the plugin `U` in place of `T` (or at the end of the set if `T` is not part of it).
+    pub fn replace<T: Any, U: ManagedPlugin + Any>(self) -> Self {
+        self.without::<U>()
+            .replace_entry::<T>(PluginEntry::of::<U>(load::<U>))
+    }
+
+    /// Replaces the loader of `T`, so that a differently built instance of `T` can be provided
+    /// before the plugins that depend on it load it.
+    pub fn replace_with<T: Any>(self, inject: fn(&Config, &mut Plugins)) -> Self {
+        self.replace_entry::<T>(PluginEntry::of::<T>(inject))
+    }
+
+    pub fn contains<T: Any>(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.type_id == Some(TypeId::of::<T>()))
+    }
+
     pub fn inject(&self, config: &Config, registry: &mut Plugins) {
-        match self {
-            Self::Core => {
-                registry.load::<BlockStorage>();
-                registry.load::<BlockDAG>();
-                registry.load::<VirtualVoting<Config>>();
-                registry.load::<TipSelection<Config>>();
-                registry.load::<Outbox>();
-                registry.load::<Inbox>();
-                registry.load::<Networking>();
-                registry.load::<Consensus<Config>>();
-                registry.load::<ConsensusRound<Config>>();
-                registry.load::<BlockFactory<Config>>();
-                registry.load::<ConsensusFeed<Config>>();
-            }
-            Self::Custom(handler) => handler(config, registry),
+        for entry in &self.entries {
+            (entry.inject)(config, registry);
+        }
+    }
+
+    fn replace_entry<T: Any>(mut self, replacement: PluginEntry) -> Self {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.type_id == Some(TypeId::of::<T>()))
+        {
+            Some(entry) => *entry = replacement,
+            None => self.entries.push(replacement),
         }
+        self
     }
 }
 
 impl Default for ProtocolPlugins {
     fn default() -> Self {
-        Self::Core
+        Self::core()
     }
 }
+
+#[derive(Clone, Copy)]
+struct PluginEntry {
+    /// The type of the plugin, which is `None` for the loaders of arbitrary plugins.
+    type_id: Option<TypeId>,
+    inject: fn(&Config, &mut Plugins),
+}
+
+impl PluginEntry {
+    fn of<T: Any>(inject: fn(&Config, &mut Plugins)) -> Self {
+        Self {
+            type_id: Some(TypeId::of::<T>()),
+            inject,
+        }
+    }
+}
+
+fn load<T: ManagedPlugin + Any>(_: &Config, registry: &mut Plugins) {
+    registry.load::<T>();
+}
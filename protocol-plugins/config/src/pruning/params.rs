@@ -0,0 +1,11 @@
+use protocol::ProtocolConfig;
+use pruning::{PruningConfig, PruningConfigParams};
+
+use crate::Config;
+
+impl PruningConfig for Config {
+    fn pruning_depth(&self) -> u64 {
+        self.params::<PruningConfigParams>()
+            .map_or(PruningConfigParams::default().depth, |params| params.depth)
+    }
+}
@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use protocol::ProtocolConfig;
+use solidifier::{SolidifierConfig, SolidifierConfigParams};
+
+use crate::Config;
+
+impl SolidifierConfig for Config {
+    fn solidifier_check_interval(&self) -> Duration {
+        Duration::from_millis(
+            self.params::<SolidifierConfigParams>()
+                .map_or(SolidifierConfigParams::default().check_interval, |params| {
+                    params.check_interval
+                }),
+        )
+    }
+
+    fn solidifier_request_timeout(&self) -> Duration {
+        Duration::from_millis(self.params::<SolidifierConfigParams>().map_or(
+            SolidifierConfigParams::default().request_timeout,
+            |params| params.request_timeout,
+        ))
+    }
+
+    fn solidifier_max_attempts(&self) -> u32 {
+        self.params::<SolidifierConfigParams>()
+            .map_or(SolidifierConfigParams::default().max_attempts, |params| {
+                params.max_attempts
+            })
+    }
+}
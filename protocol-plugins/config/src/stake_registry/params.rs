@@ -0,0 +1,12 @@
+use stake_registry::{StakeRegistry, StakeRegistryConfig};
+
+use crate::Config;
+
+impl StakeRegistryConfig for Config {
+    fn root_stake_registry(&self) -> StakeRegistry {
+        match self.protocol_params.snapshot() {
+            Some(snapshot) => snapshot.registry.clone(),
+            None => StakeRegistry::from_committee(&self.committee_selection().genesis(self)),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use common::crypto::SigningKey;
+use protocol::ProtocolConfig;
+use validator::{ValidatorConfig, ValidatorConfigParams};
+
+use crate::Config;
+
+impl ValidatorConfig for Config {
+    fn validator_key(&self) -> SigningKey {
+        let params = self
+            .params::<ValidatorConfigParams>()
+            .expect("ValidatorConfigParams not found in config");
+
+        params.signing_key.clone()
+    }
+}
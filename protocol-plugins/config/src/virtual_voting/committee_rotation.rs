@@ -0,0 +1,29 @@
+use virtual_voting::VoteBuilder;
+
+use crate::Config;
+
+#[derive(Default)]
+pub enum CommitteeRotation {
+    #[default]
+    Never,
+    EverySlots(u64),
+    EveryHeights(u64),
+    Custom(fn(&Config, &VoteBuilder<Config>) -> u64),
+}
+
+impl CommitteeRotation {
+    pub fn dispatch(&self, config: &Config, vote: &VoteBuilder<Config>) -> u64 {
+        match self {
+            Self::Never => 0,
+            // an epoch length of 0 never rotates instead of dividing by zero
+            Self::EverySlots(slots) => vote.slot.checked_div(*slots).unwrap_or(0),
+            Self::EveryHeights(heights) => vote
+                .milestone
+                .as_ref()
+                .map_or(0, |m| m.height)
+                .checked_div(*heights)
+                .unwrap_or(0),
+            Self::Custom(strategy) => strategy(config, vote),
+        }
+    }
+}
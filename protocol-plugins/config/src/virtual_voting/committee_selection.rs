@@ -1,42 +1,63 @@
 use common::{
     bft::{Committee, Member},
-    ids::IssuerID,
+    crypto::SigningKey,
 };
-use virtual_voting::{VirtualVotingConfig, Vote};
+use stake_registry::{StakeRegistry, StakeRegistryConfig};
+use virtual_voting::{Result, VirtualVotingConfig, Vote};
 
 pub enum CommitteeSelection<C: VirtualVotingConfig> {
     FixedCommittee(Committee),
+    /// Selects the `max_size` validators with the highest stake according to the registry records
+    /// in the accepted blocks, starting from the `genesis` committee.
+    StakeRegistry {
+        genesis: Committee,
+        max_size: usize,
+    },
     Custom(CustomStrategy<C>),
 }
 
-impl<C: VirtualVotingConfig> CommitteeSelection<C> {
-    pub fn dispatch(&self, config: &C, vote: Option<&Vote<C>>) -> Committee {
+impl<C: StakeRegistryConfig> CommitteeSelection<C> {
+    pub fn dispatch(&self, config: &C, vote: Option<&Vote<C>>) -> Result<Committee> {
+        let Some(vote) = vote else {
+            return Ok(self.genesis(config));
+        };
+
         match self {
-            Self::FixedCommittee(committee) => fixed_committee(committee, vote),
-            Self::Custom(strategy) => strategy(config, vote),
+            Self::FixedCommittee(_) => Ok(vote.committee.clone()),
+            Self::StakeRegistry { max_size, .. } => stake_registry(config, *max_size, vote),
+            Self::Custom(strategy) => Ok(strategy(config, Some(vote))),
         }
     }
-}
 
-fn fixed_committee<C: VirtualVotingConfig>(
-    committee: &Committee,
-    vote: Option<&Vote<C>>,
-) -> Committee {
-    match vote {
-        Some(vote) => vote.committee.clone(),
-        None => (*committee).clone(),
+    /// Returns the committee of the genesis block.
+    pub fn genesis(&self, config: &C) -> Committee {
+        match self {
+            Self::FixedCommittee(committee) => committee.clone(),
+            Self::StakeRegistry { genesis, .. } => genesis.clone(),
+            Self::Custom(strategy) => strategy(config, None),
+        }
     }
 }
 
+fn stake_registry<C: StakeRegistryConfig>(
+    config: &C,
+    max_size: usize,
+    vote: &Vote<C>,
+) -> Result<Committee> {
+    let registry = StakeRegistry::at(&vote.accepted_milestone()?, || config.root_stake_registry())?;
+
+    Ok(registry.committee(max_size))
+}
+
 type CustomStrategy<C> = fn(&C, Option<&Vote<C>>) -> Committee;
 
 impl<C: VirtualVotingConfig> Default for CommitteeSelection<C> {
     fn default() -> Self {
         CommitteeSelection::FixedCommittee(Committee::from([
-            Member::new(IssuerID::from([1u8; 32])),
-            Member::new(IssuerID::from([2u8; 32])),
-            Member::new(IssuerID::from([3u8; 32])),
-            Member::new(IssuerID::from([4u8; 32])),
+            Member::new(SigningKey::from([1u8; 32]).issuer_id()),
+            Member::new(SigningKey::from([2u8; 32]).issuer_id()),
+            Member::new(SigningKey::from([3u8; 32]).issuer_id()),
+            Member::new(SigningKey::from([4u8; 32]).issuer_id()),
         ]))
     }
 }
@@ -2,7 +2,9 @@ use virtual_voting::{Issuer, VoteBuilder};
 
 use crate::Config;
 
+#[derive(Default)]
 pub enum LeaderRotation {
+    #[default]
     RoundRobin,
     Custom(fn(&Config, &VoteBuilder<Config>) -> u64),
 }
@@ -25,9 +27,3 @@ fn round_robin(vote: &VoteBuilder<Config>) -> u64 {
         0
     }
 }
-
-impl Default for LeaderRotation {
-    fn default() -> Self {
-        Self::RoundRobin
-    }
-}
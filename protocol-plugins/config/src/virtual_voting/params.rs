@@ -1,18 +1,37 @@
 use common::bft::Committee;
-use virtual_voting::{Vote, VoteBuilder};
+use virtual_voting::{Checkpoint, Vote, VoteBuilder};
 
-use crate::{CommitteeSelection, Config, LeaderRotation, SlotDuration};
+use crate::{CommitteeRotation, CommitteeSelection, Config, LeaderRotation, SlotDuration};
 
-#[derive(Default)]
 pub struct VirtualVotingParams {
     genesis_time: u64,
     committee_selection: CommitteeSelection<Config>,
+    committee_rotation: CommitteeRotation,
     leader_rotation: LeaderRotation,
     slot_duration: SlotDuration,
     offline_threshold: u64,
+    max_time_drift: u64,
+}
+
+impl Default for VirtualVotingParams {
+    fn default() -> Self {
+        Self {
+            genesis_time: 0,
+            committee_selection: Default::default(),
+            committee_rotation: Default::default(),
+            leader_rotation: Default::default(),
+            slot_duration: Default::default(),
+            offline_threshold: 3,
+            max_time_drift: 5_000,
+        }
+    }
 }
 
 impl Config {
+    pub(crate) fn committee_selection(&self) -> &CommitteeSelection<Config> {
+        &self.virtual_voting_params.committee_selection
+    }
+
     pub fn with_genesis_time(mut self, genesis_time: u64) -> Self {
         self.virtual_voting_params.genesis_time = genesis_time;
         self
@@ -26,6 +45,11 @@ impl Config {
         self
     }
 
+    pub fn with_committee_rotation(mut self, committee_rotation: CommitteeRotation) -> Self {
+        self.virtual_voting_params.committee_rotation = committee_rotation;
+        self
+    }
+
     pub fn with_leader_rotation(mut self, leader_rotation: LeaderRotation) -> Self {
         self.virtual_voting_params.leader_rotation = leader_rotation;
         self
@@ -35,6 +59,16 @@ impl Config {
         self.virtual_voting_params.slot_duration = slot_duration;
         self
     }
+
+    pub fn with_offline_threshold(mut self, offline_threshold: u64) -> Self {
+        self.virtual_voting_params.offline_threshold = offline_threshold;
+        self
+    }
+
+    pub fn with_max_time_drift(mut self, max_time_drift: u64) -> Self {
+        self.virtual_voting_params.max_time_drift = max_time_drift;
+        self
+    }
 }
 
 impl virtual_voting::VirtualVotingConfig for Config {
@@ -42,25 +76,44 @@ impl virtual_voting::VirtualVotingConfig for Config {
         self.virtual_voting_params.genesis_time
     }
 
+    fn checkpoint(&self) -> Option<Checkpoint> {
+        self.protocol_params
+            .snapshot()
+            .map(|snapshot| snapshot.confirmed.clone())
+    }
+
     fn slot_oracle(&self, time: u64) -> u64 {
         self.virtual_voting_params
             .slot_duration
             .map_slot(self, time)
     }
 
+    fn slot_start_time(&self, slot: u64) -> u64 {
+        self.virtual_voting_params
+            .slot_duration
+            .slot_start_time(self, slot)
+    }
+
     fn offline_threshold(&self) -> u64 {
         self.virtual_voting_params.offline_threshold
     }
 
-    fn select_committee(&self, vote: Option<&Vote<Self>>) -> Committee
-    where
-        Self: Sized,
-    {
+    fn max_time_drift(&self) -> u64 {
+        self.virtual_voting_params.max_time_drift
+    }
+
+    fn select_committee(&self, vote: Option<&Vote<Self>>) -> virtual_voting::Result<Committee> {
         self.virtual_voting_params
             .committee_selection
             .dispatch(self, vote)
     }
 
+    fn committee_epoch(&self, vote: &VoteBuilder<Self>) -> u64 {
+        self.virtual_voting_params
+            .committee_rotation
+            .dispatch(self, vote)
+    }
+
     fn leader_weight(&self, vote: &VoteBuilder<Self>) -> u64 {
         self.virtual_voting_params
             .leader_rotation
@@ -1,23 +1,131 @@
+use std::collections::BTreeMap;
+
 use virtual_voting::VirtualVotingConfig as _;
 
 use crate::Config;
 
+/// Maps issuing times to slots. Slots are counted from the genesis time, and times before genesis
+/// belong to the first slot.
 pub enum SlotDuration {
     Static(u64),
-    Dynamic(fn(&Config, u64) -> u64),
+    Scheduled(SlotSchedule),
+    Custom {
+        /// Maps a time to its slot.
+        slot: fn(&Config, u64) -> u64,
+        /// Maps a slot to the time it starts at (the inverse of `slot`).
+        start_time: fn(&Config, u64) -> u64,
+    },
 }
 
 impl SlotDuration {
     pub fn map_slot(&self, config: &Config, time: u64) -> u64 {
+        let elapsed = time.saturating_sub(config.genesis_time());
         match self {
-            Self::Static(duration) => time - config.genesis_time() / duration,
-            Self::Dynamic(strategy) => strategy(config, time),
+            // a duration of 0 never advances the slot instead of dividing by zero
+            Self::Static(duration) => elapsed.checked_div(*duration).unwrap_or(0),
+            Self::Scheduled(schedule) => schedule.slot(elapsed),
+            Self::Custom { slot, .. } => slot(config, time),
         }
     }
+
+    pub fn slot_start_time(&self, config: &Config, slot: u64) -> u64 {
+        let elapsed = match self {
+            Self::Static(duration) => slot.saturating_mul(*duration),
+            Self::Scheduled(schedule) => schedule.start_time(slot),
+            Self::Custom { start_time, .. } => return start_time(config, slot),
+        };
+
+        config.genesis_time().saturating_add(elapsed)
+    }
 }
 
 impl Default for SlotDuration {
     fn default() -> Self {
-        Self::Static(10)
+        Self::Static(1_000)
+    }
+}
+
+/// Slot durations that change at the start of given epochs, where every epoch spans the same
+/// number of slots (like [`CommitteeRotation::EverySlots`](crate::CommitteeRotation::EverySlots)).
+pub struct SlotSchedule {
+    epoch_slots: u64,
+    durations: BTreeMap<u64, u64>,
+}
+
+impl SlotSchedule {
+    /// Creates a schedule whose slots last `duration` until the first change.
+    pub fn new(duration: u64, epoch_slots: u64) -> Self {
+        Self {
+            epoch_slots,
+            durations: BTreeMap::from([(0, duration.max(1))]),
+        }
+    }
+
+    /// Changes the duration of the slots from the start of the given epoch on. Durations are at
+    /// least 1 ms.
+    pub fn with_duration_from(mut self, epoch: u64, duration: u64) -> Self {
+        self.durations.insert(epoch, duration.max(1));
+        self
     }
+
+    /// Returns the slot of the given time since genesis.
+    fn slot(&self, elapsed: u64) -> u64 {
+        let mut slot = 0;
+        for phase in self.phases() {
+            if elapsed < phase.start_time {
+                break;
+            }
+            slot = phase.first_slot + (elapsed - phase.start_time) / phase.duration;
+            if phase.end_slot.is_none_or(|end_slot| slot < end_slot) {
+                break;
+            }
+        }
+
+        slot
+    }
+
+    /// Returns the time since genesis that the given slot starts at.
+    fn start_time(&self, slot: u64) -> u64 {
+        self.phases()
+            .take_while(|phase| phase.first_slot <= slot)
+            .last()
+            .map_or(0, |phase| {
+                phase
+                    .start_time
+                    .saturating_add((slot - phase.first_slot).saturating_mul(phase.duration))
+            })
+    }
+
+    /// Returns the phases of equal slot durations in order.
+    fn phases(&self) -> impl Iterator<Item = Phase> + '_ {
+        let mut start_time = 0;
+        let mut changes = self.durations.iter().peekable();
+        std::iter::from_fn(move || {
+            let (epoch, duration) = changes.next()?;
+            let first_slot = epoch.saturating_mul(self.epoch_slots);
+            let end_slot = changes
+                .peek()
+                .map(|(epoch, _)| epoch.saturating_mul(self.epoch_slots));
+
+            let phase = Phase {
+                first_slot,
+                end_slot,
+                start_time,
+                duration: *duration,
+            };
+            if let Some(end_slot) = end_slot {
+                start_time =
+                    start_time.saturating_add((end_slot - first_slot).saturating_mul(*duration));
+            }
+
+            Some(phase)
+        })
+    }
+}
+
+struct Phase {
+    first_slot: u64,
+    end_slot: Option<u64>,
+    start_time: u64,
+    duration: u64,
 }
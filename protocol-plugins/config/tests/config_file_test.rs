@@ -0,0 +1,190 @@
+use common::{crypto::SigningKey, ids::IssuerID};
+use config::{Config, ConfigError, ConfigLoader, ConfigSection, ProtocolPlugins};
+use inbox::{InboxConfig, InboxConfigParams};
+use protocol::ProtocolConfig;
+use serde::Deserialize;
+use virtual_voting::VirtualVotingConfig;
+
+fn issuer_hex(seed: u8) -> String {
+    let issuer_id = SigningKey::from([seed; 32]).issuer_id();
+    format!("{issuer_id:?}")
+}
+
+fn invalid_key(error: ConfigError) -> String {
+    match error {
+        ConfigError::InvalidValue { key, .. } => key,
+        error => panic!("expected an invalid value, got: {error}"),
+    }
+}
+
+#[test]
+fn test_load_toml() {
+    let config = ConfigLoader::default()
+        .load_toml(&format!(
+            r#"
+            genesis_time = 1_000
+            slot_duration = 100
+            offline_threshold = 7
+
+            [[committee]]
+            id = "{}"
+            weight = 3
+
+            [[committee]]
+            id = "{}"
+
+            [inbox]
+            capacity = 42
+            "#,
+            issuer_hex(1),
+            issuer_hex(2),
+        ))
+        .unwrap();
+
+    assert_eq!(config.genesis_time(), 1_000);
+    assert_eq!(config.slot_oracle(1_250), 2);
+    assert_eq!(config.offline_threshold(), 7);
+
+    let committee = config.select_committee(None).unwrap();
+    assert_eq!(committee.size(), 2);
+    assert_eq!(committee.total_weight(), 4);
+    assert_eq!(
+        committee.member_weight(&SigningKey::from([1; 32]).issuer_id()),
+        3
+    );
+
+    // unset params of a section keep their defaults
+    assert_eq!(config.inbox_capacity(), 42);
+    assert_eq!(
+        config.inbox_peer_rate(),
+        InboxConfigParams::default().peer_rate
+    );
+}
+
+#[test]
+fn test_load_json() {
+    let config = ConfigLoader::default()
+        .load_json(
+            r#"{
+                "max_time_drift": 250,
+                "slot_duration": {
+                    "duration": 100,
+                    "epoch_slots": 10,
+                    "changes": [{ "epoch": 1, "duration": 50 }]
+                },
+                "inbox": { "max_parents": 8 }
+            }"#,
+        )
+        .unwrap();
+
+    assert_eq!(config.max_time_drift(), 250);
+    assert_eq!(config.slot_start_time(10), 1_000);
+    assert_eq!(config.slot_start_time(12), 1_100);
+    assert_eq!(config.inbox_max_parents(), 8);
+}
+
+#[test]
+fn test_load_file() {
+    let dir = std::env::temp_dir();
+    let path = dir.join(format!("config-file-{}.toml", std::process::id()));
+    std::fs::write(&path, "genesis_time = 5").unwrap();
+
+    let config = Config::load(&path);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(config.unwrap().genesis_time(), 5);
+
+    assert!(matches!(
+        Config::load(dir.join("config.yaml")),
+        Err(ConfigError::UnsupportedFormat { .. })
+    ));
+    assert!(matches!(
+        Config::load(dir.join(format!("missing-{}.json", std::process::id()))),
+        Err(ConfigError::Io { .. })
+    ));
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GreeterParams {
+    greeting: String,
+}
+
+impl ConfigSection for GreeterParams {
+    const NAME: &'static str = "greeter";
+}
+
+#[test]
+fn test_plugin_sections() {
+    let content = r#"
+        [greeter]
+        greeting = "hello"
+    "#;
+
+    // sections of plugins are unknown keys until the plugin registers them
+    let error = ConfigLoader::default().load_toml(content).err().unwrap();
+    assert!(error.to_string().contains("greeter"), "{error}");
+
+    let config = ConfigLoader::default()
+        .with_section::<GreeterParams>()
+        .load_toml(content)
+        .unwrap();
+    assert_eq!(config.params::<GreeterParams>().unwrap().greeting, "hello");
+}
+
+#[test]
+fn test_plugins() {
+    let loader = ConfigLoader::default()
+        .with_plugins("none", ProtocolPlugins::core().with_loader(|_, _| {}));
+    assert!(loader.load_toml(r#"plugins = "none""#).is_ok());
+    assert!(loader.load_toml(r#"plugins = "archive""#).is_ok());
+
+    let error = loader.load_toml(r#"plugins = "all""#).err().unwrap();
+    assert_eq!(invalid_key(error), "plugins");
+}
+
+#[test]
+fn test_errors_name_the_key() {
+    let loader = ConfigLoader::default().with_section::<GreeterParams>();
+    let key = |content: &str| invalid_key(loader.load_toml(content).err().unwrap());
+
+    assert_eq!(key(r#"genesis_time = "now""#), "genesis_time");
+    assert_eq!(key("genesis_time = -1"), "genesis_time");
+    assert_eq!(key("committee = []"), "committee");
+    assert_eq!(
+        key(&format!(
+            "committee = [{{ id = \"{}\" }}, {{ id = \"0x12\" }}]",
+            issuer_hex(1)
+        )),
+        "committee[1].id"
+    );
+    assert_eq!(key("[inbox]\ncapacity = true"), "inbox.capacity");
+    assert_eq!(key("[inbox]\ncapacty = 1"), "inbox.capacty");
+    assert_eq!(key("[greeter]"), "greeter");
+    assert_eq!(key("slot_duration = { duration = 100 }"), "slot_duration");
+
+    // the message names the key and the reason
+    let error = loader.load_toml("[inbox]\ncapacity = true").err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "invalid value for `inbox.capacity`: invalid type: boolean `true`, expected usize"
+    );
+
+    assert!(matches!(
+        loader.load_toml("genesis_time ="),
+        Err(ConfigError::Syntax { .. })
+    ));
+    assert!(matches!(
+        loader.load_json("[1, 2]"),
+        Err(ConfigError::Syntax { .. })
+    ));
+}
+
+#[test]
+fn test_ids_round_trip_through_hex() {
+    let issuer_id = SigningKey::from([7; 32]).issuer_id();
+    assert_eq!(
+        format!("{issuer_id:?}").parse::<IssuerID>().unwrap(),
+        issuer_id
+    );
+    assert!("0x1234".parse::<IssuerID>().is_err());
+}
@@ -0,0 +1,125 @@
+use std::sync::Arc;
+
+use block_factory::BlockFactory;
+use block_storage::{BlockStorage, MemoryBlockStore};
+use common::crypto::SigningKey;
+use config::{Config, ProtocolParams, ProtocolPlugins};
+use networking::Networking;
+use protocol::{ManagedPlugin, Plugins, Protocol, ProtocolConfig};
+use pruning::Pruning;
+use solidifier::Solidifier;
+use tracing::{Span, info_span};
+use validator::{Validator, ValidatorConfigParams};
+
+fn protocol(plugins: ProtocolPlugins) -> Protocol {
+    Protocol::new(
+        Config::default()
+            .with_protocol_params(ProtocolParams::default().with_plugins(plugins))
+            .with_params(ValidatorConfigParams {
+                signing_key: SigningKey::from([1; 32]),
+            }),
+    )
+}
+
+fn plugin_names(protocol: &Protocol) -> Vec<&'static str> {
+    protocol
+        .plugins
+        .iter()
+        .map(|plugin| plugin.plugin_name())
+        .collect()
+}
+
+struct Relay {
+    span: Span,
+}
+
+impl ManagedPlugin for Relay {
+    fn new(_: &mut Plugins) -> Arc<Self> {
+        Arc::new(Self {
+            span: info_span!("relay"),
+        })
+    }
+
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+#[test]
+fn test_presets() {
+    let validator = protocol(ProtocolPlugins::validator());
+    assert!(validator.plugins.get::<Validator<Config>>().is_some());
+    assert!(validator.plugins.get::<Pruning<Config>>().is_some());
+    assert!(validator.plugins.get::<Solidifier<Config>>().is_some());
+
+    let observer = protocol(ProtocolPlugins::observer());
+    assert!(observer.plugins.get::<Validator<Config>>().is_none());
+    assert!(observer.plugins.get::<BlockFactory<Config>>().is_none());
+    assert!(observer.plugins.get::<Pruning<Config>>().is_some());
+    assert!(observer.plugins.get::<Solidifier<Config>>().is_some());
+
+    let archive = protocol(ProtocolPlugins::archive());
+    assert!(archive.plugins.get::<Pruning<Config>>().is_none());
+    assert!(archive.plugins.get::<Solidifier<Config>>().is_some());
+}
+
+#[test]
+fn test_with_and_without() {
+    let plugins = ProtocolPlugins::core().without::<Networking<Config>>();
+    assert!(!plugins.contains::<Networking<Config>>());
+    assert!(
+        protocol(plugins)
+            .plugins
+            .get::<Networking<Config>>()
+            .is_none()
+    );
+
+    // plugins are only added once
+    let plugins = ProtocolPlugins::core()
+        .with::<Relay>()
+        .with::<Relay>()
+        .with::<Networking<Config>>();
+    let names = plugin_names(&protocol(plugins));
+    assert_eq!(names.iter().filter(|name| **name == "Relay").count(), 1);
+    assert_eq!(names.last(), Some(&"Relay"));
+
+    // plugins that others depend on are loaded anyway
+    let plugins = ProtocolPlugins::validator().without::<BlockFactory<Config>>();
+    assert!(
+        protocol(plugins)
+            .plugins
+            .get::<BlockFactory<Config>>()
+            .is_some()
+    );
+}
+
+#[test]
+fn test_replace() {
+    let core = plugin_names(&protocol(ProtocolPlugins::core()));
+    let replaced = protocol(ProtocolPlugins::core().replace::<Networking<Config>, Relay>());
+
+    // the replacement takes the place of the replaced plugin
+    assert!(replaced.plugins.get::<Networking<Config>>().is_none());
+    let position = core.iter().position(|name| *name == "Networking").unwrap();
+    assert_eq!(plugin_names(&replaced)[position], "Relay");
+
+    // plugins that are not part of the set are added
+    let plugins = ProtocolPlugins::observer().replace::<BlockFactory<Config>, Relay>();
+    assert!(plugins.contains::<Relay>());
+}
+
+#[test]
+fn test_replace_with() {
+    // the replacement is provided before the plugins that depend on it load their own instance
+    let plugins = ProtocolPlugins::core().replace_with::<BlockStorage>(|_, registry| {
+        registry.provide(BlockStorage::with_store(Arc::new(
+            MemoryBlockStore::default(),
+        )));
+    });
+    assert!(plugins.contains::<BlockStorage>());
+
+    let protocol = protocol(plugins);
+    let block_storage = protocol.plugins.get::<BlockStorage>().unwrap();
+    assert!(block_storage.store().is_some());
+    assert_eq!(plugin_names(&protocol)[1], "BlockStorage");
+}
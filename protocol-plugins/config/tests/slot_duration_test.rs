@@ -0,0 +1,63 @@
+use config::{Config, SlotDuration, SlotSchedule};
+use virtual_voting::VirtualVotingConfig;
+
+#[test]
+fn test_static_slots_start_at_genesis() {
+    let config = Config::default()
+        .with_genesis_time(1_000)
+        .with_slot_duration(SlotDuration::Static(100));
+
+    // the genesis time is subtracted before dividing (and not divided by the duration)
+    assert_eq!(config.slot_oracle(999), 0);
+    assert_eq!(config.slot_oracle(1_000), 0);
+    assert_eq!(config.slot_oracle(1_099), 0);
+    assert_eq!(config.slot_oracle(1_100), 1);
+    assert_eq!(config.slot_oracle(2_050), 10);
+
+    assert_eq!(config.slot_start_time(0), 1_000);
+    assert_eq!(config.slot_start_time(10), 2_000);
+}
+
+#[test]
+fn test_scheduled_slots() {
+    // epochs of 10 slots that last 100 ms, then 50 ms from the 2nd epoch and 200 ms from the 4th
+    let config = Config::default()
+        .with_genesis_time(1_000)
+        .with_slot_duration(SlotDuration::Scheduled(
+            SlotSchedule::new(100, 10)
+                .with_duration_from(4, 200)
+                .with_duration_from(2, 50),
+        ));
+
+    let boundaries = [
+        (0, 1_000),
+        (19, 2_900),
+        (20, 3_000),
+        (39, 3_950),
+        (40, 4_000),
+        (41, 4_200),
+    ];
+    for (slot, start_time) in boundaries {
+        assert_eq!(config.slot_start_time(slot), start_time, "slot {slot}");
+        assert_eq!(config.slot_oracle(start_time), slot, "time {start_time}");
+        assert_eq!(
+            config.slot_oracle(start_time + 49),
+            slot,
+            "time {start_time}"
+        );
+    }
+
+    assert_eq!(config.slot_oracle(3_999), 39);
+    assert_eq!(config.slot_oracle(500), 0);
+}
+
+#[test]
+fn test_custom_slots() {
+    let config = Config::default().with_slot_duration(SlotDuration::Custom {
+        slot: |_, time| time.ilog2() as u64,
+        start_time: |_, slot| 1 << slot,
+    });
+
+    assert_eq!(config.slot_oracle(1_024), 10);
+    assert_eq!(config.slot_start_time(10), 1_024);
+}
@@ -2,7 +2,7 @@ use std::sync::{Arc, Mutex, Weak};
 
 use async_trait::async_trait;
 use common::{
-    bft::Committee,
+    bft::{Committee, Equivocation},
     rx::{Callbacks, Event, Subscription},
     up, with,
 };
@@ -60,6 +60,13 @@ impl<C: VirtualVotingConfig> ManagedPlugin for ConsensusFeed<C> {
                             ))
                         })),
                     ),
+                    equivocations: consensus.equivocations.subscribe(
+                        with!(this: move |equivocation| up!(this: {
+                            this.event.trigger(&ConsensusFeedEvent::Equivocation(
+                                equivocation.clone(),
+                            ))
+                        })),
+                    ),
                 })),
                 span: info_span!("consensus_feed"),
             }
@@ -82,6 +89,7 @@ struct Subscriptions<C: VirtualVotingConfig> {
     heaviest_milestone_vote: VoteSubscription<C>,
     latest_accepted_milestone: VoteSubscription<C>,
     committee: CommitteeSubscription,
+    equivocations: Subscription<Callbacks<Equivocation>>,
 }
 
 type U64Subscription = Subscription<Callbacks<(Option<u64>, Option<u64>)>>;
@@ -1,6 +1,6 @@
 use std::fmt::{Debug, Formatter, Result};
 
-use common::bft::Committee;
+use common::bft::{Committee, Equivocation};
 use virtual_voting::{VirtualVotingConfig, Vote};
 
 pub enum ConsensusFeedEvent<C: VirtualVotingConfig> {
@@ -8,6 +8,8 @@ pub enum ConsensusFeedEvent<C: VirtualVotingConfig> {
     HeaviestMilestoneVote(Option<Vote<C>>, Option<Vote<C>>),
     LatestAcceptedMilestone(Option<Vote<C>>, Option<Vote<C>>),
     Committee(Option<Committee>, Option<Committee>),
+    /// A committee member issued two milestones for the same round.
+    Equivocation(Equivocation),
 }
 
 impl<C: VirtualVotingConfig> Debug for ConsensusFeedEvent<C> {
@@ -27,6 +29,7 @@ impl<C: VirtualVotingConfig> Debug for ConsensusFeedEvent<C> {
                 let new = new.as_ref().map(|x| x.commitment());
                 write!(f, "Committee({:?}, {:?})", old, new)
             }
+            ConsensusFeedEvent::Equivocation(equivocation) => write!(f, "{:?}", equivocation),
         }
     }
 }
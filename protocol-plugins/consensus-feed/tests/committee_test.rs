@@ -0,0 +1,95 @@
+use std::sync::{Arc, Mutex};
+
+use block_storage::BlockStorage;
+use common::{
+    bft::{Committee, Member},
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::{BlockID, Id},
+};
+use config::{CommitteeRotation, CommitteeSelection, Config};
+use consensus::Consensus;
+use consensus_feed::{ConsensusFeed, ConsensusFeedEvent};
+use protocol::Protocol;
+use virtual_voting::Vote;
+
+fn committee(validators: &[u8]) -> Committee {
+    Committee::from(
+        validators
+            .iter()
+            .map(|seed| Member::new(SigningKey::from([*seed; 32]).issuer_id())),
+    )
+}
+
+#[tokio::test]
+async fn test_committee_rotation() {
+    fn select_committee(_: &Config, vote: Option<&Vote<Config>>) -> Committee {
+        // replace the 4th validator with a new one after the first epoch
+        match vote {
+            None => committee(&[1, 2, 3, 4]),
+            Some(_) => committee(&[1, 2, 3, 5]),
+        }
+    }
+
+    let protocol = Protocol::new(
+        Config::default()
+            .with_committee_selection(CommitteeSelection::Custom(select_committee))
+            .with_committee_rotation(CommitteeRotation::EveryHeights(2)),
+    );
+    let block_storage = protocol.plugins.get::<BlockStorage>().unwrap();
+    let consensus = protocol.plugins.get::<Consensus<Config>>().unwrap();
+    let consensus_feed = protocol.plugins.get::<ConsensusFeed<Config>>().unwrap();
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let _subscription = consensus_feed.event.subscribe({
+        let reported = reported.clone();
+        move |event| {
+            if let ConsensusFeedEvent::Committee(_, Some(committee)) = event {
+                reported
+                    .lock()
+                    .unwrap()
+                    .push(committee.commitment().clone());
+            }
+        }
+    });
+    protocol.start().await;
+
+    // let all validators (and the one that joins later) reference all blocks of the previous round
+    let mut parents: Vec<BlockID> = vec![Id::default()];
+    for round in 1..=6 {
+        parents = [1, 2, 3, 4, 5]
+            .iter()
+            .map(|seed| {
+                let block = Block::from(NetworkBlock::new(
+                    parents.clone(),
+                    round,
+                    vec![],
+                    &SigningKey::from([*seed; 32]),
+                ));
+                let id = block.id().clone();
+                block_storage.insert(block).expect("block must be new");
+                id
+            })
+            .collect();
+    }
+
+    // both the consensus and its feed report the transition to the next committee
+    let next_committee = committee(&[1, 2, 3, 5]);
+    assert_eq!(
+        consensus
+            .committee
+            .get()
+            .as_ref()
+            .map(Committee::commitment),
+        Some(next_committee.commitment())
+    );
+    assert_eq!(
+        *reported.lock().unwrap(),
+        vec![
+            committee(&[1, 2, 3, 4]).commitment().clone(),
+            next_committee.commitment().clone()
+        ]
+    );
+
+    protocol.shutdown().await;
+}
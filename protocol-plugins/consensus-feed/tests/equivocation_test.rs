@@ -0,0 +1,73 @@
+use std::sync::{Arc, Mutex};
+
+use block_storage::BlockStorage;
+use common::{
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::{BlockID, Id},
+};
+use config::Config;
+use consensus_feed::{ConsensusFeed, ConsensusFeedEvent};
+use protocol::Protocol;
+
+#[tokio::test]
+async fn test_equivocation() {
+    let protocol = Protocol::new(Config::default());
+    let block_storage = protocol.plugins.get::<BlockStorage>().unwrap();
+    let consensus_feed = protocol.plugins.get::<ConsensusFeed<Config>>().unwrap();
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let _subscription = consensus_feed.event.subscribe({
+        let reported = reported.clone();
+        move |event| {
+            if let ConsensusFeedEvent::Equivocation(equivocation) = event {
+                reported.lock().unwrap().push(equivocation.clone());
+            }
+        }
+    });
+    protocol.start().await;
+
+    let issue = |member: u8, time: u64| -> BlockID {
+        let block = Block::from(NetworkBlock::new(
+            vec![Id::default()],
+            time,
+            vec![],
+            &SigningKey::from([member; 32]),
+        ));
+        let id = block.id().clone();
+        block_storage.insert(block).expect("block must be new");
+        id
+    };
+
+    // honest members issue a single milestone per round
+    for member in 2..=4 {
+        issue(member, 1);
+    }
+    assert!(reported.lock().unwrap().is_empty());
+
+    // the first member issues two conflicting milestones for the first round
+    let first = issue(1, 1);
+    let second = issue(1, 2);
+    issue(1, 3);
+
+    // the member is only reported once per round
+    let reported = reported.lock().unwrap().clone();
+    assert_eq!(reported.len(), 1);
+    assert_eq!(
+        reported[0].issuer_id(),
+        &SigningKey::from([1; 32]).issuer_id()
+    );
+    let mut expected = vec![first, second];
+    expected.sort();
+    assert_eq!(
+        reported[0]
+            .blocks()
+            .iter()
+            .map(|block| block.id().clone())
+            .collect::<Vec<_>>(),
+        expected
+    );
+    assert!(reported[0].verify().is_ok());
+
+    protocol.shutdown().await;
+}
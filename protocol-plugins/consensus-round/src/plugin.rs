@@ -76,10 +76,10 @@ impl<C: VirtualVotingConfig> ConsensusRound<C> {
     ) -> ConsensusSubscription<C> {
         consensus.heaviest_milestone_vote.subscribe({
             move |(_, new)| {
-                if let Some(new) = new {
-                    if let Some(consensus_round) = weak.upgrade() {
-                        consensus_round.update_started(new.round);
-                    }
+                if let Some(new) = new
+                    && let Some(consensus_round) = weak.upgrade()
+                {
+                    consensus_round.update_started(new.round);
                 }
             }
         })
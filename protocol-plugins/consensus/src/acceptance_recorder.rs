@@ -0,0 +1,83 @@
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Mutex, Weak},
+};
+
+use async_trait::async_trait;
+use block_storage::{BlockStorage, BlockStore};
+use common::{
+    ids::BlockID,
+    rx::{Callbacks, Subscription},
+    up, with,
+};
+use protocol::{ManagedPlugin, Plugins};
+use tracing::{Span, error, info_span, trace};
+use virtual_voting::VirtualVotingConfig;
+
+use crate::{AcceptedBlocks, Consensus, Reorg};
+
+/// Records the accepted milestones in the [`BlockStore`] of the [`BlockStorage`], so that a
+/// restarted node can tell whether replaying its blocks led back to the same acceptance.
+pub struct AcceptanceRecorder<C: VirtualVotingConfig> {
+    store: Option<Arc<dyn BlockStore>>,
+    subscriptions: Mutex<Option<Subscriptions>>,
+    span: Span,
+    _marker: PhantomData<C>,
+}
+
+#[async_trait]
+impl<C: VirtualVotingConfig> ManagedPlugin for AcceptanceRecorder<C> {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        Arc::new_cyclic(|this: &Weak<Self>| {
+            let consensus = plugins.load::<Consensus<C>>();
+
+            Self {
+                store: plugins.load::<BlockStorage>().store().cloned(),
+                subscriptions: Mutex::new(Some(Subscriptions {
+                    accepted_blocks: consensus
+                        .accepted_blocks
+                        .subscribe(with!(this: move |accepted| up!(this: this.record(accepted)))),
+                    reorgs: consensus.reorgs.subscribe(
+                        with!(this: move |reorg| up!(this: this.record(&reorg.accepted))),
+                    ),
+                })),
+                span: info_span!("acceptance_recorder"),
+                _marker: PhantomData,
+            }
+        })
+    }
+
+    async fn shutdown(&self) {
+        self.subscriptions.lock().unwrap().take();
+    }
+
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+impl<C: VirtualVotingConfig> AcceptanceRecorder<C> {
+    fn record(&self, accepted: &AcceptedBlocks) {
+        let Some(store) = &self.store else {
+            return;
+        };
+
+        let milestones: Vec<BlockID> = accepted
+            .rounds
+            .iter()
+            .filter_map(|round| round.first())
+            .map(|milestone| milestone.block.id().clone())
+            .collect();
+
+        match store.accept_milestones(accepted.height, &milestones) {
+            Ok(()) => trace!(height = accepted.height, "accepted milestones recorded"),
+            Err(err) => error!(height = accepted.height, %err, "failed to record milestones"),
+        }
+    }
+}
+
+#[allow(dead_code)] // Subscriptions are only held to keep them alive (they act as guards)
+struct Subscriptions {
+    accepted_blocks: Subscription<Callbacks<AcceptedBlocks>>,
+    reorgs: Subscription<Callbacks<Reorg>>,
+}
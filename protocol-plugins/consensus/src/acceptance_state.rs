@@ -1,5 +1,20 @@
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct AcceptanceState {
     pub chain_id: u64,
     pub height: u64,
     pub round_index: u64,
 }
+
+/// An entry in the acceptance history of a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AcceptanceRecord {
+    pub state: AcceptanceState,
+    /// The chain index that replaced the chain this acceptance happened on.
+    pub abandoned_by: Option<u64>,
+}
+
+impl AcceptanceRecord {
+    pub fn is_valid_on(&self, chain_id: u64) -> bool {
+        self.state.chain_id <= chain_id && self.abandoned_by.is_none_or(|by| by > chain_id)
+    }
+}
@@ -5,6 +5,8 @@ use indexmap::IndexSet;
 
 pub struct AcceptedBlocks {
     pub height: u64,
+    /// The blocks accepted at the heights above `height`. The first block of a round is the
+    /// milestone that accepted it.
     pub rounds: Vec<IndexSet<BlockMetadata>>,
 }
 
@@ -1,9 +1,12 @@
-use std::sync::{Arc, Mutex, Weak};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, Weak},
+};
 
 use async_trait::async_trait;
 use block_dag::{BlockDAG, BlockMetadataExt};
 use common::{
-    bft::Committee,
+    bft::{Committee, Equivocation},
     blocks::BlockMetadata,
     down,
     rx::{
@@ -14,10 +17,13 @@ use common::{
     up, with,
 };
 use protocol::{ManagedPlugin, Plugins};
-use tracing::{Span, error, info, info_span, trace};
-use virtual_voting::{VirtualVotingConfig, Vote};
+use tracing::{Span, error, info, info_span, trace, warn};
+use virtual_voting::{Issuer, VirtualVotingConfig, Vote};
 
-use crate::{AcceptanceState, AcceptedBlocks, ConsensusMetadata};
+use crate::{
+    AcceptanceState, AcceptedBlocks, ConsensusMetadata, Reorg,
+    equivocation_detector::EquivocationDetector,
+};
 
 pub struct Consensus<C: VirtualVotingConfig> {
     pub chain_index: Variable<u64>,
@@ -25,6 +31,11 @@ pub struct Consensus<C: VirtualVotingConfig> {
     pub latest_accepted_milestone: Variable<Vote<C>>,
     pub committee: Variable<Committee>,
     pub accepted_blocks: Event<AcceptedBlocks>,
+    /// Triggered instead of `accepted_blocks` when the accepted chain is replaced.
+    pub reorgs: Event<Reorg>,
+    /// Triggered when a committee member issued two milestones for the same round.
+    pub equivocations: Event<Equivocation>,
+    equivocation_detector: Mutex<EquivocationDetector>,
     block_dag_subscription: Mutex<Option<Subscription<Callbacks<BlockMetadata>>>>,
     span: Span,
 }
@@ -35,18 +46,24 @@ impl<C: VirtualVotingConfig> ManagedPlugin for Consensus<C> {
         Arc::new_cyclic(|this: &Weak<Self>| {
             let block_dag = plugins.load::<BlockDAG>();
 
+            let chain_index = Variable::default();
+            chain_index.set(0);
+
             Self {
-                chain_index: Default::default(),
+                chain_index,
                 heaviest_milestone_vote: Default::default(),
                 latest_accepted_milestone: Default::default(),
                 committee: Default::default(),
                 accepted_blocks: Default::default(),
+                reorgs: Default::default(),
+                equivocations: Default::default(),
+                equivocation_detector: Default::default(),
                 block_dag_subscription: Mutex::new(Some(block_dag.block_available.subscribe(
                     with!(this: move |block| {
                         block.attach(down!(block: with!(this: move |vote| up!(this, block: {
                             block.set(Arc::new(ConsensusMetadata::default()));
 
-                            this.process_vote(vote).unwrap_or_else(|e| error!("{:?}", e))
+                            this.process_vote(&block, vote).unwrap_or_else(|e| error!("{:?}", e))
                         }))))
                     }),
                 ))),
@@ -65,8 +82,20 @@ impl<C: VirtualVotingConfig> ManagedPlugin for Consensus<C> {
 }
 
 impl<C: VirtualVotingConfig> Consensus<C> {
-    fn process_vote(&self, vote: &Vote<C>) -> virtual_voting::Result<()> {
+    pub fn current_chain_index(&self) -> u64 {
+        self.chain_index.get().unwrap_or(0)
+    }
+
+    /// Returns true if the block is accepted on the currently active chain.
+    pub fn is_accepted(&self, block: &BlockMetadata) -> common::errors::Result<bool> {
+        Ok(block
+            .try_get::<Arc<ConsensusMetadata>>()?
+            .is_accepted_on(self.current_chain_index()))
+    }
+
+    fn process_vote(&self, block: &BlockMetadata, vote: &Vote<C>) -> virtual_voting::Result<()> {
         if vote.milestone.is_some() {
+            self.detect_equivocation(block, vote);
             self.update_heaviest_milestone_vote(vote)?;
             self.update_latest_accepted_milestone(vote)?;
         }
@@ -74,6 +103,26 @@ impl<C: VirtualVotingConfig> Consensus<C> {
         Ok(())
     }
 
+    fn detect_equivocation(&self, block: &BlockMetadata, vote: &Vote<C>) {
+        let Issuer::User(issuer) = &vote.issuer else {
+            return;
+        };
+
+        let equivocation = {
+            let mut detector = self.equivocation_detector.lock().unwrap();
+            // conflicting milestones of accepted rounds can no longer change the outcome
+            if let Some(accepted) = self.latest_accepted_milestone.get().as_ref() {
+                detector.prune(accepted.round);
+            }
+            detector.record(issuer, vote.round, &block.block)
+        };
+
+        if let Some(equivocation) = equivocation {
+            warn!(%issuer, round = vote.round, "equivocation detected");
+            self.equivocations.trigger(&equivocation);
+        }
+    }
+
     fn update_heaviest_milestone_vote(&self, vote: &Vote<C>) -> virtual_voting::Result<()> {
         self.heaviest_milestone_vote.compute(|old| {
             let result = match old {
@@ -114,30 +163,80 @@ impl<C: VirtualVotingConfig> Consensus<C> {
     }
 
     fn advance_acceptance(&self, old: &Vote<C>, new: &Vote<C>) -> virtual_voting::Result<()> {
-        let height = old.height()?;
-        match new.height()?.checked_sub(height) {
-            None | Some(0) => panic!("TODO: implement reorg"),
-            Some(range) => {
-                let milestones = new.milestone_range(range)?;
-                let last_milestone =
-                    Vote::try_from(milestones.last().expect("must exist").prev_milestone()?)?;
-                match &last_milestone == old {
-                    false => panic!("TODO: implement reorg"),
-                    true => self
-                        .accepted_blocks
-                        .trigger(&self.accepted_blocks(height, milestones)?),
-                }
+        let common_milestone = new.common_milestone(old)?;
+        let height = common_milestone.height()?;
+        let milestones = new.milestone_range(new.height()? - height)?;
+
+        match common_milestone.is_same(old) {
+            true => self
+                .accepted_blocks
+                .trigger(&self.accepted_blocks(height, milestones)?),
+            false => {
+                let chain_index = self.increase_chain_index();
+                let reverted = self.abandon_blocks(old, height, chain_index)?;
+                info!("reorg to chain {} (height={})", chain_index, height);
+
+                self.reorgs.trigger(&Reorg {
+                    chain_index,
+                    reverted,
+                    accepted: self.accepted_blocks(height, milestones)?,
+                })
             }
         }
 
         Ok(())
     }
 
+    fn increase_chain_index(&self) -> u64 {
+        let chain_index = self.current_chain_index() + 1;
+        self.chain_index.set(chain_index);
+
+        chain_index
+    }
+
+    fn abandon_blocks(
+        &self,
+        old: &Vote<C>,
+        height: u64,
+        chain_index: u64,
+    ) -> virtual_voting::Result<AcceptedBlocks> {
+        let past_cone = old.source.try_upgrade()?.past_cone(|b| {
+            Ok(b.try_get::<Arc<ConsensusMetadata>>()?
+                .accepted_height()
+                .is_some_and(|h| h > height))
+        })?;
+
+        // group the orphaned blocks by the height and order they were accepted at
+        let mut rounds = BTreeMap::<u64, Vec<(u64, BlockMetadata)>>::new();
+        for block in past_cone {
+            let metadata = block.try_get::<Arc<ConsensusMetadata>>()?;
+            if let Some(state) = metadata.accepted.get().as_ref() {
+                rounds
+                    .entry(state.height)
+                    .or_default()
+                    .push((state.round_index, block.clone()));
+            }
+            metadata.abandon(chain_index);
+        }
+
+        Ok(AcceptedBlocks {
+            height,
+            rounds: rounds
+                .into_values()
+                .map(|mut blocks| {
+                    blocks.sort_by_key(|(round_index, _)| *round_index);
+                    blocks.into_iter().map(|(_, block)| block).collect()
+                })
+                .collect(),
+        })
+    }
+
     fn accepted_blocks(
         &self,
         height: u64,
         milestones: Vec<Vote<C>>,
     ) -> virtual_voting::Result<AcceptedBlocks> {
+        let chain_id = self.current_chain_index();
         let mut accepted_blocks = AcceptedBlocks {
             height,
             rounds: Vec::with_capacity(milestones.len()),
@@ -145,15 +244,17 @@ impl<C: VirtualVotingConfig> Consensus<C> {
 
         for (height_index, accepted_milestone) in milestones.iter().rev().enumerate() {
             let block = accepted_milestone.source.try_upgrade()?;
-            let past_cone =
-                block.past_cone(|b| Ok(!b.try_get::<Arc<ConsensusMetadata>>()?.is_accepted(0)))?;
+            let past_cone = block.past_cone(|b| {
+                Ok(!b
+                    .try_get::<Arc<ConsensusMetadata>>()?
+                    .is_accepted_on(chain_id))
+            })?;
 
             for (round_index, block) in past_cone.iter().rev().enumerate() {
                 block
                     .try_get::<Arc<ConsensusMetadata>>()?
-                    .accepted
-                    .set(AcceptanceState {
-                        chain_id: 0,
+                    .accept(AcceptanceState {
+                        chain_id,
                         height: height + (height_index + 1) as u64,
                         round_index: round_index as u64,
                     });
@@ -0,0 +1,57 @@
+use std::collections::{BTreeMap, HashMap, hash_map::Entry};
+
+use common::{bft::Equivocation, blocks::Block, ids::IssuerID};
+use tracing::warn;
+
+/// Remembers the milestone of every committee member per round, so that members that issue two
+/// conflicting milestones for the same round are detected.
+#[derive(Default)]
+pub(crate) struct EquivocationDetector {
+    rounds: BTreeMap<u64, HashMap<IssuerID, RoundMilestone>>,
+}
+
+struct RoundMilestone {
+    block: Block,
+    reported: bool,
+}
+
+impl EquivocationDetector {
+    /// Records the milestone of the issuer and returns the evidence if the issuer already issued
+    /// another milestone for the same round. Every issuer is reported at most once per round.
+    pub(crate) fn record(
+        &mut self,
+        issuer: &IssuerID,
+        round: u64,
+        block: &Block,
+    ) -> Option<Equivocation> {
+        let milestone = match self.rounds.entry(round).or_default().entry(issuer.clone()) {
+            Entry::Vacant(entry) => {
+                entry.insert(RoundMilestone {
+                    block: block.clone(),
+                    reported: false,
+                });
+                return None;
+            }
+            Entry::Occupied(entry) => entry.into_mut(),
+        };
+        if milestone.reported || milestone.block.id() == block.id() {
+            return None;
+        }
+
+        match Equivocation::new(milestone.block.clone(), block.clone()) {
+            Ok(equivocation) => {
+                milestone.reported = true;
+                Some(equivocation)
+            }
+            Err(err) => {
+                warn!(%issuer, round, %err, "invalid evidence");
+                None
+            }
+        }
+    }
+
+    /// Forgets the milestones of the rounds before the given one.
+    pub(crate) fn prune(&mut self, round: u64) {
+        self.rounds = self.rounds.split_off(&round);
+    }
+}
@@ -1,6 +1,12 @@
+mod acceptance_recorder;
 mod acceptance_state;
 mod accepted_blocks;
 mod consensus;
+mod equivocation_detector;
 mod metadata;
+mod reorg;
 
-pub use crate::{acceptance_state::*, accepted_blocks::*, consensus::*, metadata::*};
+pub use crate::{
+    acceptance_recorder::*, acceptance_state::*, accepted_blocks::*, consensus::*, metadata::*,
+    reorg::*,
+};
@@ -1,17 +1,55 @@
-use common::rx::Signal;
+use std::sync::Mutex;
 
-use crate::AcceptanceState;
+use common::rx::Variable;
+
+use crate::{AcceptanceRecord, AcceptanceState};
 
 #[derive(Default)]
 pub struct ConsensusMetadata {
-    pub accepted: Signal<AcceptanceState>,
+    /// The acceptance state on the currently active chain.
+    pub accepted: Variable<AcceptanceState>,
+    history: Mutex<Vec<AcceptanceRecord>>,
 }
 
 impl ConsensusMetadata {
-    pub fn is_accepted(&self, chain_id: u64) -> bool {
-        self.accepted
-            .get()
-            .as_ref()
-            .is_some_and(|a| a.chain_id == chain_id)
+    pub fn is_accepted(&self) -> bool {
+        self.accepted.get().is_some()
+    }
+
+    pub fn is_accepted_on(&self, chain_id: u64) -> bool {
+        self.history
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|record| record.is_valid_on(chain_id))
+    }
+
+    /// Returns true if the block was accepted on a chain that has since been abandoned and hasn't
+    /// been accepted again.
+    pub fn is_abandoned(&self) -> bool {
+        !self.is_accepted() && !self.history.lock().unwrap().is_empty()
+    }
+
+    pub fn accepted_height(&self) -> Option<u64> {
+        self.accepted.get().as_ref().map(|a| a.height)
+    }
+
+    pub fn history(&self) -> Vec<AcceptanceRecord> {
+        self.history.lock().unwrap().clone()
+    }
+
+    pub(crate) fn accept(&self, state: AcceptanceState) {
+        self.history.lock().unwrap().push(AcceptanceRecord {
+            state: state.clone(),
+            abandoned_by: None,
+        });
+        self.accepted.set(state);
+    }
+
+    pub(crate) fn abandon(&self, chain_id: u64) {
+        if let Some(record) = self.history.lock().unwrap().last_mut() {
+            record.abandoned_by.get_or_insert(chain_id);
+        }
+        self.accepted.unset();
     }
 }
@@ -0,0 +1,10 @@
+use crate::AcceptedBlocks;
+
+/// Emitted when the accepted chain switches to a milestone that does not descend from the
+/// previously accepted one.
+#[derive(Debug)]
+pub struct Reorg {
+    pub chain_index: u64,
+    pub reverted: AcceptedBlocks,
+    pub accepted: AcceptedBlocks,
+}
@@ -0,0 +1,28 @@
+use consensus::{AcceptanceRecord, AcceptanceState};
+
+fn record(chain_id: u64, abandoned_by: Option<u64>) -> AcceptanceRecord {
+    AcceptanceRecord {
+        state: AcceptanceState {
+            chain_id,
+            height: 1,
+            round_index: 0,
+        },
+        abandoned_by,
+    }
+}
+
+#[test]
+fn test_acceptance_record() {
+    // accepted on chain 0 and never abandoned: valid on every later chain
+    let record1 = record(0, None);
+    assert!(record1.is_valid_on(0));
+    assert!(record1.is_valid_on(5));
+
+    // accepted on chain 1 and abandoned by chain 3
+    let record2 = record(1, Some(3));
+    assert!(!record2.is_valid_on(0));
+    assert!(record2.is_valid_on(1));
+    assert!(record2.is_valid_on(2));
+    assert!(!record2.is_valid_on(3));
+    assert!(!record2.is_valid_on(4));
+}
@@ -0,0 +1,203 @@
+use std::sync::{Arc, Mutex};
+
+use block_storage::BlockStorage;
+use common::{
+    blocks::{Block, BlockMetadata, NetworkBlock},
+    crypto::SigningKey,
+    ids::{BlockID, Id},
+};
+use config::{Config, SlotDuration};
+use consensus::{Consensus, ConsensusMetadata};
+use protocol::Protocol;
+
+/// Issues the blocks of two partitions of the committee, which both accept their own milestones
+/// once they consider the other half offline.
+struct Partitions {
+    protocol: Protocol,
+    block_storage: Arc<BlockStorage>,
+}
+
+impl Partitions {
+    async fn new() -> Self {
+        let protocol = Protocol::new(
+            Config::default()
+                .with_genesis_time(100)
+                .with_slot_duration(SlotDuration::Static(10))
+                .with_offline_threshold(1),
+        );
+        protocol.start().await;
+
+        Self {
+            block_storage: protocol.plugins.get().unwrap(),
+            protocol,
+        }
+    }
+
+    fn consensus(&self) -> Arc<Consensus<Config>> {
+        self.protocol.plugins.get().unwrap()
+    }
+
+    /// Issues a block for every member of the committee at slot 0 and returns their ids.
+    fn issue_slot_0(&self) -> Vec<BlockID> {
+        (1..=4)
+            .map(|member| self.issue(member, 101, vec![Id::default()]))
+            .collect()
+    }
+
+    /// Issues a block per slot for both members of the partition and returns the blocks of each
+    /// slot.
+    fn issue_partition(
+        &self,
+        members: [u8; 2],
+        slots: u64,
+        mut parents: Vec<BlockID>,
+    ) -> Vec<Vec<BlockID>> {
+        (1..=slots)
+            .map(|slot| {
+                let time = 100 + slot * 10 + 5;
+                parents = members
+                    .iter()
+                    .map(|member| self.issue(*member, time, parents.clone()))
+                    .collect();
+                parents.clone()
+            })
+            .collect()
+    }
+
+    fn issue(&self, member: u8, time: u64, parents: Vec<BlockID>) -> BlockID {
+        let block = Block::from(NetworkBlock::new(
+            parents,
+            time,
+            vec![],
+            &SigningKey::from([member; 32]),
+        ));
+        let id = block.id().clone();
+        self.block_storage.insert(block).expect("block must be new");
+        id
+    }
+
+    fn block(&self, id: &BlockID) -> BlockMetadata {
+        self.block_storage.get(id).unwrap()
+    }
+
+    fn metadata(&self, id: &BlockID) -> Arc<ConsensusMetadata> {
+        self.block(id).try_get::<Arc<ConsensusMetadata>>().unwrap()
+    }
+
+    fn accepted_height(&self) -> u64 {
+        let consensus = self.consensus();
+        let vote = consensus.latest_accepted_milestone.get();
+        vote.as_ref().unwrap().height().unwrap()
+    }
+}
+
+#[tokio::test]
+async fn test_reorg() {
+    let partitions = Partitions::new().await;
+    let consensus = partitions.consensus();
+
+    let reorgs = Arc::new(Mutex::new(Vec::new()));
+    let _subscription = consensus.reorgs.subscribe({
+        let reorgs = reorgs.clone();
+        move |reorg| {
+            reorgs.lock().unwrap().push((
+                reorg.chain_index,
+                reorg.reverted.height,
+                reorg
+                    .reverted
+                    .rounds
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<_>>(),
+                reorg
+                    .accepted
+                    .rounds
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            ))
+        }
+    });
+
+    let slot_0 = partitions.issue_slot_0();
+
+    // the first partition accepts its own milestones on chain 0
+    let chain_a = partitions.issue_partition([1, 2], 4, slot_0.clone());
+    assert_eq!(partitions.accepted_height(), 3);
+    assert_eq!(consensus.current_chain_index(), 0);
+    let orphaned = &chain_a[0][0];
+    assert!(consensus.is_accepted(&partitions.block(orphaned)).unwrap());
+    assert!(reorgs.lock().unwrap().is_empty());
+
+    // the heavier chain of the second partition replaces it
+    let chain_b = partitions.issue_partition([3, 4], 6, slot_0);
+    assert_eq!(partitions.accepted_height(), 5);
+    assert_eq!(consensus.current_chain_index(), 1);
+
+    let reorgs = std::mem::take(&mut *reorgs.lock().unwrap());
+    assert_eq!(reorgs.len(), 1);
+    let (chain_index, height, reverted, accepted) = &reorgs[0];
+    assert_eq!(*chain_index, 1);
+    assert_eq!(*height, 1);
+    assert!(reverted.contains(&partitions.block(orphaned)));
+    assert!(accepted.contains(&partitions.block(&chain_b[0][0])));
+    assert!(!accepted.contains(&partitions.block(orphaned)));
+
+    // the blocks of the first partition are rolled back, the ones that both partitions saw are
+    // accepted again on the new chain
+    for block in reverted {
+        let metadata = block.try_get::<Arc<ConsensusMetadata>>().unwrap();
+        assert_eq!(
+            consensus.is_accepted(block).unwrap(),
+            accepted.contains(block)
+        );
+        assert_eq!(metadata.is_accepted(), accepted.contains(block));
+    }
+    assert!(!partitions.metadata(orphaned).is_accepted());
+    assert!(partitions.metadata(&chain_b[0][0]).is_accepted());
+    assert!(
+        consensus
+            .is_accepted(&partitions.block(&chain_b[0][0]))
+            .unwrap()
+    );
+
+    partitions.protocol.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_reorg_rolls_back_acceptance_history() {
+    let partitions = Partitions::new().await;
+    let slot_0 = partitions.issue_slot_0();
+    let chain_a = partitions.issue_partition([1, 2], 4, slot_0.clone());
+    partitions.issue_partition([3, 4], 6, slot_0.clone());
+    assert_eq!(partitions.consensus().current_chain_index(), 1);
+
+    // a block that only the first partition accepted stays valid on its abandoned chain only
+    let orphaned = partitions.metadata(&chain_a[0][0]);
+    let history = orphaned.history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].state.chain_id, 0);
+    assert_eq!(history[0].abandoned_by, Some(1));
+    assert!(orphaned.is_abandoned());
+    assert!(orphaned.is_accepted_on(0));
+    assert!(!orphaned.is_accepted_on(1));
+
+    // a block that both partitions accepted above the common milestone keeps both records
+    let reaccepted = slot_0
+        .iter()
+        .map(|id| partitions.metadata(id))
+        .find(|metadata| metadata.history().len() == 2)
+        .expect("a block of slot 0 must be accepted on both chains");
+    let history = reaccepted.history();
+    assert_eq!(history[0].state.chain_id, 0);
+    assert_eq!(history[0].abandoned_by, Some(1));
+    assert_eq!(history[1].state.chain_id, 1);
+    assert_eq!(history[1].abandoned_by, None);
+    assert!(!reaccepted.is_abandoned());
+    assert!(reaccepted.is_accepted_on(0));
+    assert!(reaccepted.is_accepted_on(1));
+
+    partitions.protocol.shutdown().await;
+}
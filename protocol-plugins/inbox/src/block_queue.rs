@@ -0,0 +1,268 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        Condvar, Mutex, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+use common::{bft::Committee, blocks::Block, networking::PeerID};
+use tracing::debug;
+
+use crate::{Error, Result};
+
+/// The name of the gauge that reports the number of queued blocks.
+pub const DEPTH_METRIC: &str = "inbox_depth";
+
+/// The name of the counter that is incremented for every dropped block, labeled with the
+/// `reason` of the drop.
+pub const DROPPED_BLOCKS_METRIC: &str = "inbox_dropped_blocks";
+
+/// A bounded queue of blocks that schedules the blocks of different sources (the node itself and
+/// its peers) in a round-robin fashion, so that a flooding peer cannot delay the blocks of
+/// others.
+///
+/// Every peer is limited by a token bucket. When the queue is full, blocks of committee members
+/// (and of the node itself) replace queued blocks of other issuers, taken from the source with
+/// the most of them. All other blocks are dropped. The issuer of a block is only trusted for this
+/// once its signature was checked, so that peers cannot evict blocks by claiming to be a member.
+pub struct BlockQueue {
+    state: Mutex<QueueState>,
+    available: Condvar,
+    committee: RwLock<Option<Committee>>,
+    capacity: usize,
+    peer_rate: u32,
+    peer_burst: u32,
+    dropped: AtomicU64,
+}
+
+/// The source of a block: the peer that sent it, or `None` if it was issued by the node itself.
+type Source = Option<PeerID>;
+
+#[derive(Default)]
+struct QueueState {
+    queues: HashMap<Source, SourceQueue>,
+    /// The sources with queued blocks, in the order in which they are served.
+    ready: VecDeque<Source>,
+    len: usize,
+    buckets: HashMap<PeerID, TokenBucket>,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct SourceQueue {
+    prioritized: VecDeque<Block>,
+    others: VecDeque<Block>,
+}
+
+impl BlockQueue {
+    pub fn new(capacity: usize, peer_rate: u32, peer_burst: u32) -> Self {
+        Self {
+            state: Default::default(),
+            available: Condvar::new(),
+            committee: RwLock::new(None),
+            capacity,
+            peer_rate,
+            peer_burst,
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Prefers the blocks of the members of the given committee from now on. As long as no
+    /// committee is set, only the blocks of the node itself are preferred.
+    pub fn set_committee(&self, committee: Committee) {
+        *self.committee.write().unwrap() = Some(committee);
+    }
+
+    /// Queues a block of the given source (or of the node itself if it is `None`).
+    pub fn push(&self, source: Option<PeerID>, block: Block) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return Err(Error::Closed);
+        }
+
+        if let Some(peer) = source {
+            // genesis blocks are not signed, so peers could use them to inject arbitrary roots
+            if let Block::GenesisBlock(_) = block {
+                return Err(self.drop_block(&block, source, Error::GenesisBlock));
+            }
+
+            let bucket = state
+                .buckets
+                .entry(peer)
+                .or_insert_with(|| TokenBucket::new(self.peer_rate, self.peer_burst));
+            if !bucket.try_take(Instant::now()) {
+                return Err(self.drop_block(&block, source, Error::RateLimited));
+            }
+        }
+
+        let prioritized = self.is_prioritized(source, &block);
+        if state.len >= self.capacity {
+            // the signature is only checked here, as blocks are verified by the workers anyway
+            let authentic = source.is_none() || block.verify_signature().is_ok();
+            match (prioritized && authentic).then(|| state.evict()).flatten() {
+                Some((evicted_source, evicted)) => {
+                    self.drop_block(&evicted, evicted_source, Error::Full);
+                }
+                None => return Err(self.drop_block(&block, source, Error::Full)),
+            }
+        }
+
+        let queue = state.queues.entry(source).or_default();
+        let was_empty = queue.is_empty();
+        match prioritized {
+            true => queue.prioritized.push_back(block),
+            false => queue.others.push_back(block),
+        }
+        if was_empty {
+            state.ready.push_back(source);
+        }
+        state.len += 1;
+        metrics::gauge!(DEPTH_METRIC).set(state.len as f64);
+
+        self.available.notify_one();
+        Ok(())
+    }
+
+    /// Takes the next block, blocking until one is available. Returns `None` once the queue is
+    /// closed and all queued blocks were taken.
+    pub fn pop_blocking(&self) -> Option<Block> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(block) = state.pop() {
+                metrics::gauge!(DEPTH_METRIC).set(state.len as f64);
+                return Some(block);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.available.wait(state).unwrap();
+        }
+    }
+
+    /// Rejects all further blocks and wakes up the consumers, which take the remaining blocks.
+    pub fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.available.notify_all();
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of blocks that were dropped because the queue was full or their peer
+    /// exceeded its rate limit.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Blocks of the node itself and of committee members are prioritized. As long as the
+    /// committee is unknown, only the blocks of the node itself are.
+    fn is_prioritized(&self, source: Source, block: &Block) -> bool {
+        let Block::NetworkBlock(_, network_block) = block else {
+            return source.is_none();
+        };
+
+        source.is_none()
+            || self
+                .committee
+                .read()
+                .unwrap()
+                .as_ref()
+                .is_some_and(|committee| committee.member(&network_block.issuer_id).is_some())
+    }
+
+    /// Counts the block as dropped for the given reason, which is returned.
+    pub(crate) fn drop_block(&self, block: &Block, source: Source, reason: Error) -> Error {
+        self.dropped.fetch_add(1, Ordering::Relaxed);
+        metrics::counter!(DROPPED_BLOCKS_METRIC, "reason" => reason.as_str()).increment(1);
+        match source {
+            Some(peer) => debug!(id = %block.id(), %peer, %reason, "block dropped"),
+            None => debug!(id = %block.id(), %reason, "block dropped"),
+        }
+
+        reason
+    }
+}
+
+impl QueueState {
+    fn pop(&mut self) -> Option<Block> {
+        let source = self.ready.pop_front()?;
+        let queue = self.queues.get_mut(&source)?;
+        let block = queue
+            .prioritized
+            .pop_front()
+            .or_else(|| queue.others.pop_front())?;
+
+        if queue.is_empty() {
+            self.queues.remove(&source);
+        } else {
+            self.ready.push_back(source);
+        }
+        self.len -= 1;
+
+        Some(block)
+    }
+
+    /// Removes the most recent block that is not prioritized from the source with the most of
+    /// them.
+    fn evict(&mut self) -> Option<(Source, Block)> {
+        let (source, queue) = self
+            .queues
+            .iter_mut()
+            .filter(|(_, queue)| !queue.others.is_empty())
+            .max_by_key(|(_, queue)| queue.others.len())?;
+        let source = *source;
+        let block = queue.others.pop_back()?;
+
+        if queue.is_empty() {
+            self.queues.remove(&source);
+            self.ready.retain(|ready| *ready != source);
+        }
+        self.len -= 1;
+
+        Some((source, block))
+    }
+}
+
+impl SourceQueue {
+    fn is_empty(&self) -> bool {
+        self.prioritized.is_empty() && self.others.is_empty()
+    }
+}
+
+/// Limits a peer to `rate` blocks per second, with bursts of up to `burst` blocks.
+struct TokenBucket {
+    tokens: f64,
+    rate: f64,
+    burst: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u32, burst: u32) -> Self {
+        Self {
+            tokens: burst as f64,
+            rate: rate as f64,
+            burst: burst as f64,
+            refilled: Instant::now(),
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.refilled = now;
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
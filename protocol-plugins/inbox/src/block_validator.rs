@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+use common::{blocks::Block, ids::BlockID};
+
+use crate::ValidationError;
+
+/// Validates the blocks of peers before the [`Inbox`](crate::Inbox) queues them.
+///
+/// Validators only see the block itself, so that rejected blocks never allocate an address in the
+/// block storage. Additional validators are added with
+/// [`Inbox::register_validator`](crate::Inbox::register_validator).
+pub trait BlockValidator: Send + Sync {
+    fn validate(&self, block: &Block) -> Result<(), ValidationError>;
+}
+
+/// The rules that every block of a peer has to follow.
+///
+/// Peers must not send genesis blocks, and their blocks reference at least one and at most
+/// `max_parents` distinct parents. The genesis block is the root of every other block, so it can
+/// only be referenced as the single parent of a block.
+pub struct SyntacticValidator {
+    max_parents: usize,
+}
+
+impl SyntacticValidator {
+    pub fn new(max_parents: usize) -> Self {
+        Self { max_parents }
+    }
+}
+
+impl BlockValidator for SyntacticValidator {
+    fn validate(&self, block: &Block) -> Result<(), ValidationError> {
+        let Block::NetworkBlock(_, network_block) = block else {
+            return Err(ValidationError::GenesisBlock);
+        };
+
+        let parents = &network_block.parents;
+        if parents.is_empty() {
+            return Err(ValidationError::NoParents);
+        }
+        if parents.len() > self.max_parents {
+            return Err(ValidationError::TooManyParents {
+                count: parents.len(),
+                max: self.max_parents,
+            });
+        }
+
+        let mut distinct = HashSet::with_capacity(parents.len());
+        for parent in parents {
+            if !distinct.insert(parent) {
+                return Err(ValidationError::DuplicateParent(parent.clone()));
+            }
+        }
+
+        if parents.len() > 1 && distinct.contains(&BlockID::default()) {
+            return Err(ValidationError::GenesisWithOtherParents);
+        }
+
+        Ok(())
+    }
+}
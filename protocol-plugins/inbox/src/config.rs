@@ -0,0 +1,15 @@
+use virtual_voting::VirtualVotingConfig;
+
+pub trait InboxConfig: VirtualVotingConfig {
+    /// The maximum number of blocks that are queued for processing.
+    fn inbox_capacity(&self) -> usize;
+
+    /// The number of blocks per second that every peer may send on average.
+    fn inbox_peer_rate(&self) -> u32;
+
+    /// The number of blocks that a peer may send at once before it is limited to its rate.
+    fn inbox_peer_burst(&self) -> u32;
+
+    /// The maximum number of parents that a block of a peer may reference.
+    fn inbox_max_parents(&self) -> usize;
+}
@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InboxConfigParams {
+    /// The maximum number of blocks that are queued for processing.
+    pub capacity: usize,
+    /// The number of blocks per second that every peer may send on average.
+    pub peer_rate: u32,
+    /// The number of blocks that a peer may send at once before it is limited to its rate.
+    pub peer_burst: u32,
+    /// The maximum number of parents that a block of a peer may reference.
+    pub max_parents: usize,
+}
+
+impl Default for InboxConfigParams {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            peer_rate: 1_000,
+            peer_burst: 1_000,
+            max_parents: 64,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use common::ids::BlockID;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum Error {
+    #[error("Inbox is closed")]
+    Closed,
+
+    #[error("Peer exceeded its rate limit")]
+    RateLimited,
+
+    #[error("Inbox is full")]
+    Full,
+
+    #[error("Genesis blocks are not accepted from peers")]
+    GenesisBlock,
+
+    #[error("Time {time} is too far in the future (now: {now}, max drift: {max_drift})")]
+    TimeTooFarInFuture { time: u64, now: u64, max_drift: u64 },
+
+    #[error("Invalid block: {0}")]
+    Invalid(#[from] ValidationError),
+}
+
+impl Error {
+    /// The label of the error in the dropped blocks metric.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Error::Closed => "closed",
+            Error::RateLimited => "rate_limited",
+            Error::Full => "full",
+            Error::GenesisBlock => "genesis_block",
+            Error::TimeTooFarInFuture { .. } => "time_too_far_in_future",
+            Error::Invalid(err) => err.as_str(),
+        }
+    }
+}
+
+/// The reasons for which a [`BlockValidator`](crate::BlockValidator) rejects a block.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ValidationError {
+    #[error("Genesis blocks are not accepted from peers")]
+    GenesisBlock,
+
+    #[error("Block has no parents")]
+    NoParents,
+
+    #[error("Block has {count} parents (max: {max})")]
+    TooManyParents { count: usize, max: usize },
+
+    #[error("Parent `{0}` is referenced more than once")]
+    DuplicateParent(BlockID),
+
+    #[error("Genesis block is referenced next to other parents")]
+    GenesisWithOtherParents,
+
+    #[error("Block violates rule `{rule}`: {reason}")]
+    Rule { rule: &'static str, reason: String },
+}
+
+impl ValidationError {
+    /// The label of the error in the dropped blocks metric.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationError::GenesisBlock => "genesis_block",
+            ValidationError::NoParents => "no_parents",
+            ValidationError::TooManyParents { .. } => "too_many_parents",
+            ValidationError::DuplicateParent(_) => "duplicate_parent",
+            ValidationError::GenesisWithOtherParents => "genesis_with_other_parents",
+            ValidationError::Rule { rule, .. } => rule,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
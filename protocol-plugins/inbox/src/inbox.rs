@@ -2,54 +2,102 @@ use std::sync::{Arc, Mutex, RwLock};
 
 use async_trait::async_trait;
 use block_storage::BlockStorage;
-use common::{blocks::Block, down, extensions::ArcExt, up, with};
+use clock::ProtocolClock;
+use common::{
+    bft::Committee,
+    blocks::Block,
+    down,
+    errors::Error as BlockError,
+    extensions::ArcExt,
+    networking::PeerID,
+    rx::{Callbacks, Subscription},
+    up, with,
+};
+use consensus::Consensus;
 use protocol::{ManagedPlugin, Plugins};
-use tokio::{
-    sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
-    task,
-    task::JoinHandle,
+use tokio::{task, task::JoinHandle};
+use tracing::{Instrument, Span, debug, error, info, info_span, trace, warn};
+
+use crate::{
+    BlockQueue, BlockValidator, Error, InboxConfig, Result, SyntacticValidator, ValidationError,
 };
-use tracing::{Instrument, Span, debug, error, info, info_span, trace};
 
-pub struct Inbox {
-    sender: RwLock<Option<UnboundedSender<Block>>>,
-    receiver: Arc<Mutex<UnboundedReceiver<Block>>>,
+/// Verifies received blocks and inserts them into the [`BlockStorage`].
+///
+/// Blocks are processed from a bounded [`BlockQueue`] that serves the node and its peers in turn.
+/// Peers are rate limited, and blocks of the members of the committee of the [`Consensus`] are
+/// preferred when the queue is full. Blocks of peers that fail a [`BlockValidator`] or that are
+/// issued further in the future than the configured max time drift are dropped before they are
+/// queued.
+pub struct Inbox<C: InboxConfig> {
+    queue: Arc<BlockQueue>,
+    validators: RwLock<Vec<Arc<dyn BlockValidator>>>,
+    config: Arc<C>,
+    clock: Arc<ProtocolClock>,
+    committee_subscription: Mutex<Option<Subscription<Callbacks<CommitteeUpdate>>>>,
     num_workers: usize,
     block_storage: Arc<BlockStorage>,
+    consensus: Arc<Consensus<C>>,
     span: Span,
     worker_handles: tokio::sync::Mutex<Option<Vec<JoinHandle<()>>>>,
 }
 
 #[async_trait]
-impl ManagedPlugin for Inbox {
+impl<C: InboxConfig> ManagedPlugin for Inbox<C> {
     fn new(plugins: &mut Plugins) -> Arc<Self> {
-        let (tx, rx) = unbounded_channel();
+        let config = plugins.get::<C>().unwrap();
+
         Arc::new(Self {
-            sender: RwLock::new(Some(tx)),
-            receiver: Arc::new(Mutex::new(rx)),
+            queue: Arc::new(BlockQueue::new(
+                config.inbox_capacity(),
+                config.inbox_peer_rate(),
+                config.inbox_peer_burst(),
+            )),
+            validators: RwLock::new(vec![Arc::new(SyntacticValidator::new(
+                config.inbox_max_parents(),
+            ))]),
+            config,
+            clock: plugins.load(),
+            committee_subscription: Mutex::new(None),
             num_workers: 2,
             block_storage: plugins.load(),
+            consensus: plugins.load(),
             span: info_span!("inbox"),
             worker_handles: tokio::sync::Mutex::new(None),
         })
     }
 
     async fn start(&self) {
+        let queue = Arc::clone(&self.queue);
+        *self.committee_subscription.lock().unwrap() =
+            Some(self.consensus.committee.subscribe(move |(_, committee)| {
+                if let Some(committee) = committee {
+                    queue.set_committee(committee.clone());
+                }
+            }));
+
         let num_workers = self.num_workers;
         let block_storage = self.block_storage.clone();
-        let rx = Arc::clone(&self.receiver);
+        let queue = Arc::clone(&self.queue);
 
         let mut worker_handles = Vec::new();
         for i in 0..num_workers {
-            let handle = tokio::spawn(with!(block_storage, rx: async move {
+            let handle = tokio::spawn(with!(block_storage, queue: async move {
                 let worker_span = Span::current();
-                let worker_task = task::spawn_blocking(with!(worker_span: down!(block_storage, rx: move || {
-                    up!(block_storage, rx: worker_span.in_scope(|| {
+                let worker_task = task::spawn_blocking(with!(worker_span: down!(block_storage, queue: move || {
+                    up!(block_storage, queue: worker_span.in_scope(|| {
                         debug!("worker started");
-                        while let Some(block) = rx.lock().unwrap().blocking_recv() {
+                        while let Some(block) = queue.pop_blocking() {
                             info_span!("block", id = %block.id()).in_scope(|| {
                                 debug!("block received");
-                                block_storage.insert(block);
+                                // most duplicates are skipped without verifying them again, but
+                                // only the insert decides which of two concurrent copies is stored
+                                if block_storage.get(block.id()).is_some()
+                                    || Self::verify_signature(&block)
+                                        && block_storage.insert(block).is_none()
+                                {
+                                    trace!("block already stored");
+                                }
                             })
                         }
                         debug!("worker stopped");
@@ -69,7 +117,8 @@ impl ManagedPlugin for Inbox {
 
     async fn shutdown(&self) {
         trace!("shutting down");
-        self.sender.write().unwrap().take();
+        self.committee_subscription.lock().unwrap().take();
+        self.queue.close();
 
         if let Some(worker_handles) = self.worker_handles.lock().await.take() {
             for worker in worker_handles {
@@ -84,12 +133,83 @@ impl ManagedPlugin for Inbox {
     }
 }
 
-impl Inbox {
-    pub fn send(&self, block: Block) -> Result<(), tokio::sync::mpsc::error::SendError<Block>> {
-        if let Some(sender) = &*self.sender.read().unwrap() {
-            sender.send(block)
-        } else {
-            Err(tokio::sync::mpsc::error::SendError(block))
+impl<C: InboxConfig> Inbox<C> {
+    /// Queues a block that was issued by the node itself. Such blocks are not rate limited.
+    pub fn send(&self, block: Block) -> Result<()> {
+        self.queue.push(None, block)
+    }
+
+    /// Queues a block that was received from the given peer.
+    pub fn send_from(&self, peer: PeerID, block: Block) -> Result<()> {
+        if let Err(reason) = self.validate(&block) {
+            return Err(self.queue.drop_block(&block, Some(peer), reason.into()));
+        }
+
+        if let Block::NetworkBlock(_, network_block) = &block {
+            let now = self.clock.now();
+            let max_drift = self.config.max_time_drift();
+            if network_block.issuing_time > now.saturating_add(max_drift) {
+                let reason = Error::TimeTooFarInFuture {
+                    time: network_block.issuing_time,
+                    now,
+                    max_drift,
+                };
+                return Err(self.queue.drop_block(&block, Some(peer), reason));
+            }
+        }
+
+        self.queue.push(Some(peer), block)
+    }
+
+    /// Adds a validator that the blocks of peers have to pass in addition to the syntactic rules.
+    pub fn register_validator(&self, validator: Arc<dyn BlockValidator>) {
+        self.validators.write().unwrap().push(validator);
+    }
+
+    /// Returns the number of queued blocks.
+    pub fn depth(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns the number of blocks that were dropped because the inbox was full or their peer
+    /// exceeded its rate limit.
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped()
+    }
+
+    fn validate(&self, block: &Block) -> std::result::Result<(), ValidationError> {
+        self.validators
+            .read()
+            .unwrap()
+            .iter()
+            .try_for_each(|validator| validator.validate(block))
+    }
+
+    fn verify_signature(block: &Block) -> bool {
+        match block.verify_signature() {
+            Ok(()) => true,
+            Err(BlockError::InvalidPublicKey { issuer_id, .. }) => {
+                warn!(issuer = %issuer_id, reason = "invalid public key", "block dropped");
+                false
+            }
+            Err(BlockError::InvalidSignature { issuer_id, .. }) => {
+                warn!(issuer = %issuer_id, reason = "invalid signature", "block dropped");
+                false
+            }
+            Err(err) => {
+                warn!(reason = %err, "block dropped");
+                false
+            }
         }
     }
 }
+
+impl<C: InboxConfig> Drop for Inbox<C> {
+    fn drop(&mut self) {
+        // the workers only hold the queue, so they would wait for blocks forever if the inbox is
+        // dropped without being shut down (e.g. when a test panics)
+        self.queue.close();
+    }
+}
+
+type CommitteeUpdate = (Option<Committee>, Option<Committee>);
@@ -1,3 +1,10 @@
+mod block_queue;
+mod block_validator;
+mod config;
+mod config_params;
+mod error;
 mod inbox;
 
-pub use crate::inbox::*;
+pub use crate::{
+    block_queue::*, block_validator::*, config::*, config_params::*, error::*, inbox::*,
+};
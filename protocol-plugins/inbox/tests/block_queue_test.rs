@@ -0,0 +1,141 @@
+use common::{
+    bft::{Committee, Member},
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::BlockID,
+    networking::PeerID,
+};
+use inbox::{BlockQueue, Error};
+
+fn block(signer: u8, issuing_time: u64) -> Block {
+    NetworkBlock::new(
+        vec![],
+        issuing_time,
+        vec![],
+        &SigningKey::from([signer; 32]),
+    )
+    .into()
+}
+
+#[test]
+fn test_sources_are_served_round_robin() {
+    let queue = BlockQueue::new(100, 1_000, 1_000);
+
+    // a flooding peer does not delay the blocks of others
+    let flood: Vec<Block> = (0..3).map(|i| block(1, i)).collect();
+    for block in &flood {
+        queue.push(Some(PeerID(1)), block.clone()).unwrap();
+    }
+    let other = block(2, 0);
+    queue.push(Some(PeerID(2)), other.clone()).unwrap();
+    let own = block(3, 0);
+    queue.push(None, own.clone()).unwrap();
+
+    let order: Vec<Block> = (0..5).map(|_| queue.pop_blocking().unwrap()).collect();
+    let ids: Vec<_> = order.iter().map(|block| block.id().clone()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            flood[0].id().clone(),
+            other.id().clone(),
+            own.id().clone(),
+            flood[1].id().clone(),
+            flood[2].id().clone(),
+        ]
+    );
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_peers_are_rate_limited() {
+    let queue = BlockQueue::new(100, 1, 2);
+
+    assert_eq!(queue.push(Some(PeerID(1)), block(1, 0)), Ok(()));
+    assert_eq!(queue.push(Some(PeerID(1)), block(1, 1)), Ok(()));
+    assert_eq!(
+        queue.push(Some(PeerID(1)), block(1, 2)),
+        Err(Error::RateLimited)
+    );
+
+    // the bucket is per peer, and blocks of the node itself are never limited
+    assert_eq!(queue.push(Some(PeerID(2)), block(1, 3)), Ok(()));
+    for i in 0..10 {
+        assert_eq!(queue.push(None, block(1, 10 + i)), Ok(()));
+    }
+    assert_eq!(queue.len(), 13);
+    assert_eq!(queue.dropped(), 1);
+}
+
+#[test]
+fn test_committee_blocks_evict_others() {
+    let member = SigningKey::from([1; 32]);
+    let queue = BlockQueue::new(2, 1_000, 1_000);
+    queue.set_committee(Committee::from(vec![Member::new(member.issuer_id())]));
+
+    let others = [block(2, 0), block(2, 1)];
+    for other in &others {
+        queue.push(Some(PeerID(2)), other.clone()).unwrap();
+    }
+    assert_eq!(queue.push(Some(PeerID(2)), block(2, 2)), Err(Error::Full));
+
+    // the most recent block of another issuer makes room for the block of the member
+    let member_block: Block = NetworkBlock::new(vec![], 0, vec![], &member).into();
+    assert_eq!(queue.push(Some(PeerID(3)), member_block.clone()), Ok(()));
+    assert_eq!(queue.len(), 2);
+
+    // a block that claims to be issued by the member without a valid signature evicts nothing
+    let mut forged = NetworkBlock::new(vec![], 1, vec![], &SigningKey::from([2; 32]));
+    forged.issuer_id = member.issuer_id();
+    assert_eq!(queue.push(Some(PeerID(4)), forged.into()), Err(Error::Full));
+
+    let remaining: Vec<_> = (0..2)
+        .map(|_| queue.pop_blocking().unwrap().id().clone())
+        .collect();
+    assert_eq!(
+        remaining,
+        vec![others[0].id().clone(), member_block.id().clone()]
+    );
+    assert_eq!(queue.dropped(), 3);
+}
+
+#[test]
+fn test_blocks_of_peers_are_not_prioritized_without_committee() {
+    let queue = BlockQueue::new(1, 1_000, 1_000);
+
+    queue.push(Some(PeerID(1)), block(1, 0)).unwrap();
+    assert_eq!(queue.push(Some(PeerID(2)), block(2, 0)), Err(Error::Full));
+
+    // blocks of the node itself are
+    assert_eq!(queue.push(None, block(3, 0)), Ok(()));
+    assert_eq!(queue.len(), 1);
+}
+
+#[test]
+fn test_genesis_blocks_of_peers_are_dropped() {
+    let queue = BlockQueue::new(100, 1_000, 1_000);
+    let genesis = Block::GenesisBlock(BlockID::from([7; 32]));
+
+    assert_eq!(
+        queue.push(Some(PeerID(1)), genesis.clone()),
+        Err(Error::GenesisBlock)
+    );
+    assert_eq!(queue.dropped(), 1);
+
+    // the node itself still issues its own genesis block
+    assert_eq!(queue.push(None, genesis), Ok(()));
+}
+
+#[test]
+fn test_closed_queue() {
+    let queue = BlockQueue::new(100, 1_000, 1_000);
+    let queued = block(1, 0);
+    queue.push(None, queued.clone()).unwrap();
+
+    queue.close();
+    assert_eq!(queue.push(None, block(1, 1)), Err(Error::Closed));
+    assert_eq!(queue.push(Some(PeerID(1)), block(1, 2)), Err(Error::Closed));
+
+    // queued blocks are still taken before the consumers are released
+    assert_eq!(queue.pop_blocking().unwrap().id(), queued.id());
+    assert!(queue.pop_blocking().is_none());
+}
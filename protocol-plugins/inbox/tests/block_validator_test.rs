@@ -0,0 +1,49 @@
+use common::{
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::BlockID,
+};
+use inbox::{BlockValidator, SyntacticValidator, ValidationError};
+
+fn block(parents: Vec<BlockID>) -> Block {
+    NetworkBlock::new(parents, 0, vec![], &SigningKey::from([1; 32])).into()
+}
+
+#[test]
+fn test_syntactic_rules() {
+    let validator = SyntacticValidator::new(2);
+    let genesis = BlockID::default();
+    let parent = BlockID::from([1; 32]);
+    let other = BlockID::from([2; 32]);
+
+    assert_eq!(validator.validate(&block(vec![genesis.clone()])), Ok(()));
+    assert_eq!(
+        validator.validate(&block(vec![parent.clone(), other.clone()])),
+        Ok(())
+    );
+
+    assert_eq!(
+        validator.validate(&Block::GenesisBlock(genesis.clone())),
+        Err(ValidationError::GenesisBlock)
+    );
+    assert_eq!(
+        validator.validate(&block(vec![])),
+        Err(ValidationError::NoParents)
+    );
+    assert_eq!(
+        validator.validate(&block(vec![
+            parent.clone(),
+            other.clone(),
+            BlockID::from([3; 32])
+        ])),
+        Err(ValidationError::TooManyParents { count: 3, max: 2 })
+    );
+    assert_eq!(
+        validator.validate(&block(vec![parent.clone(), parent.clone()])),
+        Err(ValidationError::DuplicateParent(parent.clone()))
+    );
+    assert_eq!(
+        validator.validate(&block(vec![genesis, parent])),
+        Err(ValidationError::GenesisWithOtherParents)
+    );
+}
@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+use inbox::InboxConfig;
+
+pub trait NetworkingConfig: InboxConfig {
+    /// The maximum number of peers that a block is announced to.
+    fn gossip_fanout(&self) -> usize;
+
+    /// The number of recently seen blocks that are remembered to suppress duplicates.
+    fn gossip_seen_capacity(&self) -> usize;
+
+    /// The time after which a block that was requested but not received is requested again from
+    /// another peer that has it.
+    fn gossip_request_timeout(&self) -> Duration;
+}
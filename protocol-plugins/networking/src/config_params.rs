@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkingConfigParams {
+    /// The maximum number of peers that a block is announced to.
+    pub gossip_fanout: usize,
+    /// The number of recently seen blocks that are remembered to suppress duplicates.
+    pub gossip_seen_capacity: usize,
+    /// The time (in milliseconds) after which a block that was requested but not received is
+    /// requested again from another peer that has it.
+    pub gossip_request_timeout: u64,
+}
+
+impl Default for NetworkingConfigParams {
+    fn default() -> Self {
+        Self {
+            gossip_fanout: 8,
+            gossip_seen_capacity: 100_000,
+            gossip_request_timeout: 1_000,
+        }
+    }
+}
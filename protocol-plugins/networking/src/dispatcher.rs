@@ -0,0 +1,35 @@
+use common::{
+    bft::Equivocation,
+    blocks::Block,
+    ids::BlockID,
+    networking::{Message, PeerID},
+    rx::Event,
+};
+use tracing::trace;
+
+/// Dispatches received messages, together with the peer that sent them, to the plugins that
+/// subscribed to their variant.
+#[derive(Default)]
+pub struct Dispatcher {
+    pub blocks: Event<(PeerID, Block)>,
+    pub block_announcements: Event<(PeerID, BlockID)>,
+    pub block_requests: Event<(PeerID, BlockID)>,
+    pub block_responses: Event<(PeerID, Block)>,
+    pub equivocations: Event<(PeerID, Equivocation)>,
+}
+
+impl Dispatcher {
+    pub(crate) fn dispatch(&self, peer: PeerID, message: Message) {
+        trace!("received message {:?} from {}", message, peer);
+
+        match message {
+            Message::Block(block) => self.blocks.trigger(&(peer, block)),
+            Message::BlockAnnouncement(id) => self.block_announcements.trigger(&(peer, id)),
+            Message::BlockRequest(id) => self.block_requests.trigger(&(peer, id)),
+            Message::BlockResponse(block) => self.block_responses.trigger(&(peer, block)),
+            Message::Equivocation(equivocation) => {
+                self.equivocations.trigger(&(peer, equivocation))
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use common::{ids::BlockID, networking::PeerID};
+
+/// Decides which peers a block is announced to and which announcements are answered with a
+/// request.
+///
+/// Every block is announced by its id once, to at most `fanout` peers that are not known to have
+/// it already (because it was received from them or announced by them). Peers that miss an
+/// announced block request it, so that the full block only travels to the peers that need it.
+/// Requests that are not answered within the request timeout are [sent again](Gossip::expired) to
+/// another peer that announced the block.
+pub struct Gossip {
+    fanout: usize,
+    request_timeout: Duration,
+    seen: Mutex<SeenBlocks>,
+}
+
+impl Gossip {
+    pub fn new(fanout: usize, seen_capacity: usize, request_timeout: Duration) -> Self {
+        Self {
+            fanout,
+            request_timeout,
+            seen: Mutex::new(SeenBlocks::new(seen_capacity)),
+        }
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// Records that the peer announced the block and returns whether it should be requested from
+    /// the peer.
+    pub fn announced(&self, peer: PeerID, block_id: &BlockID) -> bool {
+        let mut seen = self.seen.lock().unwrap();
+        let block = seen.entry(block_id);
+        block.holders.insert(peer);
+
+        let request = match block.requested {
+            _ if block.received => false,
+            Some((requested, _)) if requested.elapsed() < self.request_timeout => false,
+            _ => {
+                block.request_from(peer);
+                true
+            }
+        };
+        if request {
+            seen.pending.insert(block_id.clone());
+        }
+
+        request
+    }
+
+    /// Records that the block was received from the peer.
+    pub fn received(&self, peer: PeerID, block_id: &BlockID) {
+        let mut seen = self.seen.lock().unwrap();
+        let block = seen.entry(block_id);
+        block.holders.insert(peer);
+        block.received = true;
+        seen.pending.remove(block_id);
+    }
+
+    /// Records that the received block was dropped before it was processed, so that it is requested
+    /// again from the next peer that announces it.
+    pub fn dropped(&self, block_id: &BlockID) {
+        let mut seen = self.seen.lock().unwrap();
+        let block = seen.entry(block_id);
+        block.received = false;
+        block.requested = None;
+        seen.pending.remove(block_id);
+    }
+
+    /// Returns the requests that were not answered within the request timeout, each addressed to
+    /// another peer that announced the block. Peers that were not asked yet are preferred, and
+    /// requests without another holder wait for the next announcement.
+    pub fn expired(&self) -> Vec<(PeerID, BlockID)> {
+        let mut seen = self.seen.lock().unwrap();
+        let SeenBlocks {
+            blocks, pending, ..
+        } = &mut *seen;
+
+        let mut requests = Vec::new();
+        for block_id in pending.iter() {
+            let Some(block) = blocks.get_mut(block_id) else {
+                continue;
+            };
+            let Some((requested, requested_from)) = block.requested else {
+                continue;
+            };
+            if requested.elapsed() < self.request_timeout {
+                continue;
+            }
+
+            let candidates = |asked: &HashSet<PeerID>| {
+                block
+                    .holders
+                    .iter()
+                    .filter(|peer| **peer != requested_from && !asked.contains(peer))
+                    .min()
+                    .copied()
+            };
+            let next = candidates(&block.asked).or_else(|| candidates(&HashSet::new()));
+            if let Some(peer) = next {
+                block.request_from(peer);
+                requests.push((peer, block_id.clone()));
+            }
+        }
+
+        requests
+    }
+
+    /// Returns the peers that the block should be announced to. Blocks that were announced before
+    /// are not announced again.
+    pub fn recipients(&self, block_id: &BlockID, peers: &[PeerID]) -> Vec<PeerID> {
+        let mut seen = self.seen.lock().unwrap();
+        let rotation = seen.next_rotation();
+        seen.pending.remove(block_id);
+        let block = seen.entry(block_id);
+        if block.announced {
+            return Vec::new();
+        }
+        block.announced = true;
+        block.received = true;
+
+        let candidates: Vec<PeerID> = peers
+            .iter()
+            .filter(|peer| !block.holders.contains(peer))
+            .copied()
+            .collect();
+        if candidates.len() <= self.fanout {
+            return candidates;
+        }
+
+        // rotate the selection, so that consecutive blocks are announced to different peers
+        let offset = rotation % candidates.len();
+        candidates
+            .iter()
+            .cycle()
+            .skip(offset)
+            .take(self.fanout)
+            .copied()
+            .collect()
+    }
+}
+
+/// The most recently seen blocks, of which the oldest are forgotten once the capacity is
+/// exceeded.
+struct SeenBlocks {
+    blocks: HashMap<BlockID, SeenBlock>,
+    order: VecDeque<BlockID>,
+    /// The blocks that were requested but not received yet.
+    pending: HashSet<BlockID>,
+    capacity: usize,
+    rotation: usize,
+}
+
+#[derive(Default)]
+struct SeenBlock {
+    /// The peers that are known to have the block.
+    holders: HashSet<PeerID>,
+    received: bool,
+    announced: bool,
+    /// When and from which peer the block was last requested.
+    requested: Option<(Instant, PeerID)>,
+    /// The peers that the block was requested from.
+    asked: HashSet<PeerID>,
+}
+
+impl SeenBlock {
+    fn request_from(&mut self, peer: PeerID) {
+        self.requested = Some((Instant::now(), peer));
+        self.asked.insert(peer);
+    }
+}
+
+impl SeenBlocks {
+    fn new(capacity: usize) -> Self {
+        Self {
+            blocks: HashMap::new(),
+            order: VecDeque::new(),
+            pending: HashSet::new(),
+            capacity,
+            rotation: 0,
+        }
+    }
+
+    fn entry(&mut self, block_id: &BlockID) -> &mut SeenBlock {
+        if !self.blocks.contains_key(block_id) {
+            while self.order.len() >= self.capacity.max(1) {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.blocks.remove(&oldest);
+                    self.pending.remove(&oldest);
+                }
+            }
+            self.order.push_back(block_id.clone());
+        }
+
+        self.blocks.entry(block_id.clone()).or_default()
+    }
+
+    fn next_rotation(&mut self) -> usize {
+        self.rotation = self.rotation.wrapping_add(1);
+        self.rotation
+    }
+}
@@ -1,3 +1,7 @@
+mod config;
+mod config_params;
+mod dispatcher;
+mod gossip;
 mod networking;
 
-pub use crate::networking::*;
+pub use crate::{config::*, config_params::*, dispatcher::*, gossip::*, networking::*};
@@ -1,14 +1,23 @@
-use std::sync::Arc;
+use std::{
+    marker::PhantomData,
+    sync::{Arc, RwLock, Weak},
+};
 
 use async_trait::async_trait;
+use block_storage::BlockStorage;
 use common::{
+    bft::Equivocation,
     blocks::Block,
-    networking::{Endpoint, Network},
-    traced,
+    ids::BlockID,
+    networking::{Endpoint, Message, Network, PeerID, Recipients},
+    rx::{Callbacks, Event, Subscription, Variable},
+    traced::{self, WorkerFailure},
+    up, with,
 };
-use inbox::Inbox;
+use consensus::Consensus;
+use inbox::{Inbox, InboxConfig};
 use outbox::Outbox;
-use protocol::{ManagedPlugin, Plugins};
+use protocol::{Health, ManagedPlugin, Plugins};
 use tokio::{
     sync::{
         Mutex, MutexGuard,
@@ -17,29 +26,104 @@ use tokio::{
         watch::Receiver,
     },
     task::JoinHandle,
+    time::MissedTickBehavior,
 };
-use tracing::{Level, Span, error, info_span, span, trace};
+use tracing::{Level, Span, debug, error, info_span, span, trace};
+
+use crate::{Dispatcher, Gossip, NetworkingConfig};
 
-pub struct Networking {
-    inbox: Arc<Inbox>,
+/// Connects the protocol to a [`Network`].
+///
+/// Received messages are dispatched to the subscribers of their variant in the [`Dispatcher`].
+/// Blocks and block responses are delivered to the [`Inbox`], and block requests are answered from
+/// the [`BlockStorage`].
+///
+/// Blocks of the [`Outbox`] are announced by their id to the peers selected by the [`Gossip`], and
+/// announced blocks that are missing are requested from the peer that announced them. Requests
+/// that go unanswered are sent again to another peer that announced the block.
+///
+/// Equivocations that the [`Consensus`] detects are sent to all peers. The blocks of received
+/// evidence are delivered to the [`Inbox`], so that every node detects the equivocation itself.
+pub struct Networking<C: NetworkingConfig> {
+    pub dispatcher: Arc<Dispatcher>,
+    /// The peers that messages can currently be sent to.
+    pub peers: Arc<Variable<Vec<PeerID>>>,
+    gossip: Arc<Gossip>,
     outbox: Arc<Outbox>,
-    workers: Mutex<Option<(JoinHandle<()>, JoinHandle<()>, watch::Sender<()>)>>,
+    block_storage: Arc<BlockStorage>,
+    sender: RwLock<Option<UnboundedSender<(Recipients, Message)>>>,
+    workers: Mutex<Option<Workers>>,
+    worker_failures: Event<WorkerFailure>,
+    subscriptions: std::sync::Mutex<Option<Subscriptions>>,
     span: Span,
+    _marker: PhantomData<C>,
 }
 
 #[async_trait]
-impl ManagedPlugin for Networking {
+impl<C: NetworkingConfig> ManagedPlugin for Networking<C> {
     fn new(plugins: &mut Plugins) -> Arc<Self> {
-        Arc::new(Self {
-            inbox: plugins.load(),
-            outbox: plugins.load(),
-            workers: Mutex::new(None),
-            span: info_span!("networking"),
+        Arc::new_cyclic(|this: &Weak<Self>| {
+            let inbox = plugins.load::<Inbox<C>>();
+            let consensus = plugins.load::<Consensus<C>>();
+            let config = plugins.get::<C>().unwrap();
+            let gossip = Arc::new(Gossip::new(
+                config.gossip_fanout(),
+                config.gossip_seen_capacity(),
+                config.gossip_request_timeout(),
+            ));
+            let dispatcher = Arc::new(Dispatcher::default());
+
+            Self {
+                subscriptions: std::sync::Mutex::new(Some(Subscriptions {
+                    blocks: dispatcher.blocks.subscribe(with!(inbox, gossip: move |(peer, block)| {
+                        receive_block(&inbox, &gossip, *peer, block)
+                    })),
+                    block_announcements: dispatcher.block_announcements.subscribe(
+                        with!(this: move |(peer, id)| up!(this: this.answer_block_announcement(*peer, id))),
+                    ),
+                    block_requests: dispatcher.block_requests.subscribe(
+                        with!(this: move |(peer, id)| up!(this: this.answer_block_request(*peer, id))),
+                    ),
+                    block_responses: dispatcher.block_responses.subscribe(
+                        with!(inbox, gossip: move |(peer, block)| receive_block(&inbox, &gossip, *peer, block)),
+                    ),
+                    received_equivocations: dispatcher.equivocations.subscribe(
+                        with!(gossip: move |(peer, equivocation)| {
+                            for block in equivocation.blocks() {
+                                receive_block(&inbox, &gossip, *peer, block)
+                            }
+                        }),
+                    ),
+                    detected_equivocations: consensus.equivocations.subscribe(
+                        with!(this: move |equivocation| up!(this: {
+                            this.send(Message::Equivocation(equivocation.clone()))
+                        })),
+                    ),
+                })),
+                dispatcher,
+                peers: Default::default(),
+                gossip,
+                outbox: plugins.load(),
+                block_storage: plugins.load(),
+                sender: RwLock::new(None),
+                workers: Mutex::new(None),
+                worker_failures: plugins.worker_failures(),
+                span: info_span!("networking"),
+                _marker: PhantomData,
+            }
         })
     }
 
     async fn shutdown(&self) {
         self.disconnect().await;
+        self.subscriptions.lock().unwrap().take();
+    }
+
+    fn health(&self) -> Health {
+        match self.sender.read().unwrap().is_some() {
+            true => Health::Healthy,
+            false => Health::Degraded("not connected".to_string()),
+        }
     }
 
     fn span(&self) -> Span {
@@ -47,28 +131,81 @@ impl ManagedPlugin for Networking {
     }
 }
 
-impl Networking {
+impl<C: NetworkingConfig> Networking<C> {
     pub async fn connect<N: Network>(&self, network: &N) {
-        let Endpoint { inbound, outbound } = network.endpoint().await;
+        let Endpoint {
+            inbound,
+            outbound,
+            peers,
+        } = network.endpoint().await;
         let mut workers = self.workers.lock().await;
         self.shutdown_workers(&mut workers).await;
 
         let (shutdown_signal, is_shutdown) = watch::channel(());
         *workers = Some((
             self.inbound_worker(inbound, is_shutdown.clone()),
-            self.outbound_worker(outbound, is_shutdown.clone()),
+            self.outbound_worker(outbound.clone(), peers, is_shutdown.clone()),
             shutdown_signal,
         ));
+        *self.sender.write().unwrap() = Some(outbound);
     }
 
     pub async fn disconnect(&self) {
+        self.sender.write().unwrap().take();
         self.shutdown_workers(&mut self.workers.lock().await).await;
     }
 
-    async fn shutdown_workers(
-        &self,
-        workers: &mut MutexGuard<'_, Option<(JoinHandle<()>, JoinHandle<()>, watch::Sender<()>)>>,
-    ) {
+    /// Sends a message to all connected peers.
+    pub fn send(&self, message: Message) {
+        self.send_to(Recipients::All, message);
+    }
+
+    /// Sends a message to the given peers.
+    pub fn send_to(&self, recipients: Recipients, message: Message) {
+        let Some(sender) = self.sender.read().unwrap().clone() else {
+            debug!("not connected, dropping message {:?}", message);
+            return;
+        };
+
+        let description = format!("{:?}", message);
+        if let Err(e) = sender.send((recipients, message)) {
+            error!("failed to send message {}: {:?}", description, e);
+        } else {
+            trace!("sent message {}", description);
+        }
+    }
+
+    /// Asks the connected peers for the block with the given id. Peers that know the block answer
+    /// with it, and the response is delivered to the [`Inbox`] like any other block.
+    pub fn request_block(&self, block_id: &BlockID) {
+        self.send(Message::BlockRequest(block_id.clone()));
+    }
+
+    fn answer_block_announcement(&self, peer: PeerID, block_id: &BlockID) {
+        if self.gossip.announced(peer, block_id) && self.block_storage.get(block_id).is_none() {
+            self.send_to(
+                Recipients::Peers(vec![peer]),
+                Message::BlockRequest(block_id.clone()),
+            );
+        }
+    }
+
+    /// Answers a block request from the [`BlockStorage`]. Root blocks (genesis and the roots of a
+    /// snapshot) are local to every node and have no wire format, so they are never served.
+    fn answer_block_request(&self, peer: PeerID, block_id: &BlockID) {
+        match self.block_storage.get(block_id) {
+            Some(metadata) if matches!(metadata.block, Block::GenesisBlock(_)) => {
+                trace!("ignoring request for root block (id={:?})", block_id)
+            }
+            Some(metadata) => self.send_to(
+                Recipients::Peers(vec![peer]),
+                Message::BlockResponse(metadata.block.clone()),
+            ),
+            None => trace!("ignoring request for unknown block (id={:?})", block_id),
+        }
+    }
+
+    async fn shutdown_workers(&self, workers: &mut MutexGuard<'_, Option<Workers>>) {
         if let Some((inbound_worker, outbound_worker, shutdown)) = workers.take() {
             drop(shutdown); // close the shutdown channel to signal workers to stop
             // wait for workers to finish
@@ -79,54 +216,110 @@ impl Networking {
 
     fn inbound_worker(
         &self,
-        mut receiver: UnboundedReceiver<Block>,
+        mut receiver: UnboundedReceiver<(PeerID, Message)>,
         mut is_shutdown: Receiver<()>,
     ) -> JoinHandle<()> {
-        let inbox = self.inbox.clone();
+        let dispatcher = self.dispatcher.clone();
         traced::worker(
             async move {
                 loop {
                     tokio::select! {
-                        Some(block) = receiver.recv() => {
-                            let id = block.id().clone();
-                            if let Err(e) = inbox.send(block) {
-                                error!("failed to receive block (id={:?}): {:?}", id, e);
-                            } else {
-                                trace!("received block (id={:?})", id);
-                            }
-                        },
+                        Some((peer, message)) = receiver.recv() => dispatcher.dispatch(peer, message),
                         _ = is_shutdown.changed() => break, // channel closed = shutdown
                     }
                 }
             },
             span!(parent: self.span.clone(), Level::INFO, "inbound"),
+            &self.worker_failures,
         )
     }
 
     fn outbound_worker(
         &self,
-        sender: UnboundedSender<Block>,
+        sender: UnboundedSender<(Recipients, Message)>,
+        mut peers: Receiver<Vec<PeerID>>,
         mut is_shutdown: Receiver<()>,
     ) -> JoinHandle<()> {
         let outbox = self.outbox.clone();
+        let gossip = self.gossip.clone();
+        let block_storage = self.block_storage.clone();
+        let known_peers = self.peers.clone();
+        let mut sweep = tokio::time::interval(gossip.request_timeout());
+        sweep.set_missed_tick_behavior(MissedTickBehavior::Delay);
         traced::worker(
             async move {
                 let mut outbox = outbox.receiver.lock().await;
                 loop {
+                    let current_peers = peers.borrow_and_update().clone();
+                    let is_empty = current_peers.is_empty();
+                    known_peers.set_if_none_or(current_peers, |old, new| old != new);
+
+                    // keep the blocks in the outbox while there is no peer to announce them to
+                    if is_empty {
+                        tokio::select! {
+                            _ = peers.changed() => continue,
+                            _ = is_shutdown.changed() => break, // channel closed = shutdown
+                        }
+                    }
+
                     tokio::select! {
                         Some(block) = outbox.recv() => {
                             let id = block.id().clone();
-                            if let Err(e) = sender.send(block) {
-                                error!("failed to send block (id={:?}): {:?}", id, e);
+                            let recipients = gossip.recipients(&id, &peers.borrow());
+                            if recipients.is_empty() {
+                                trace!("block already known to all peers (id={:?})", id);
+                            } else if let Err(e) = sender.send((
+                                Recipients::Peers(recipients),
+                                Message::BlockAnnouncement(id.clone()),
+                            )) {
+                                error!("failed to announce block (id={:?}): {:?}", id, e);
                             } else {
-                                trace!("sent block (id={:?})", id);
+                                trace!("announced block (id={:?})", id);
+                            }
+                        },
+                        _ = sweep.tick() => {
+                            // ask another peer for the blocks whose requests went unanswered
+                            for (peer, id) in gossip.expired() {
+                                if block_storage.get(&id).is_none() {
+                                    debug!("requesting block again (id={:?}, peer={})", id, peer);
+                                    let _ = sender.send((
+                                        Recipients::Peers(vec![peer]),
+                                        Message::BlockRequest(id),
+                                    ));
+                                }
                             }
                         },
+                        _ = peers.changed() => continue,
                         _ = is_shutdown.changed() => break, // channel closed = shutdown
                     }
                 }
             },
             span!(parent: self.span.clone(), Level::INFO, "outbound"),
+            &self.worker_failures,
         )
     }
 }
+
+fn receive_block<C: InboxConfig>(inbox: &Inbox<C>, gossip: &Gossip, peer: PeerID, block: &Block) {
+    let id = block.id().clone();
+    gossip.received(peer, &id);
+    if let Err(e) = inbox.send_from(peer, block.clone()) {
+        // forget that the block was received, so that it is requested again when announced
+        gossip.dropped(&id);
+        debug!("failed to receive block (id={:?}): {}", id, e);
+    } else {
+        trace!("received block (id={:?})", id);
+    }
+}
+
+type Workers = (JoinHandle<()>, JoinHandle<()>, watch::Sender<()>);
+
+#[allow(dead_code)] // Subscriptions are only held to keep them alive (they act as guards)
+struct Subscriptions {
+    blocks: Subscription<Callbacks<(PeerID, Block)>>,
+    block_announcements: Subscription<Callbacks<(PeerID, BlockID)>>,
+    block_requests: Subscription<Callbacks<(PeerID, BlockID)>>,
+    block_responses: Subscription<Callbacks<(PeerID, Block)>>,
+    received_equivocations: Subscription<Callbacks<(PeerID, Equivocation)>>,
+    detected_equivocations: Subscription<Callbacks<Equivocation>>,
+}
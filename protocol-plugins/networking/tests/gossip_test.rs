@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use common::{ids::Id, networking::PeerID};
+use networking::Gossip;
+
+fn gossip() -> Gossip {
+    Gossip::new(8, 100_000, Duration::from_secs(1))
+}
+
+fn peers(count: u64) -> Vec<PeerID> {
+    (0..count).map(PeerID).collect()
+}
+
+#[test]
+fn test_recipients() {
+    let gossip = Gossip::new(2, 100_000, Duration::from_secs(1));
+    let block_id = Id::from([1; 32]);
+
+    // blocks are never announced back to the peer they were received from
+    gossip.received(PeerID(0), &block_id);
+    let recipients = gossip.recipients(&block_id, &peers(4));
+    assert_eq!(recipients.len(), 2);
+    assert!(!recipients.contains(&PeerID(0)));
+
+    // and only announced once
+    assert!(gossip.recipients(&block_id, &peers(4)).is_empty());
+}
+
+#[test]
+fn test_announced() {
+    let gossip = gossip();
+    let block_id = Id::from([1; 32]);
+
+    // a missing block is requested from the first peer that announces it
+    assert!(gossip.announced(PeerID(0), &block_id));
+    assert!(!gossip.announced(PeerID(1), &block_id));
+
+    // known blocks are not requested, and not announced to the peers that announced them
+    gossip.received(PeerID(0), &block_id);
+    assert!(!gossip.announced(PeerID(2), &block_id));
+    assert_eq!(gossip.recipients(&block_id, &peers(4)), vec![PeerID(3)]);
+}
+
+#[test]
+fn test_seen_capacity() {
+    let gossip = Gossip::new(8, 1, Duration::from_secs(1));
+
+    gossip.received(PeerID(0), &Id::from([1; 32]));
+    gossip.received(PeerID(0), &Id::from([2; 32]));
+
+    // the first block was forgotten and is requested again
+    assert!(gossip.announced(PeerID(0), &Id::from([1; 32])));
+}
+
+#[test]
+fn test_dropped_blocks_are_requested_again() {
+    let gossip = gossip();
+    let block_id = Id::from([1; 32]);
+
+    assert!(gossip.announced(PeerID(0), &block_id));
+    gossip.received(PeerID(0), &block_id);
+    assert!(!gossip.announced(PeerID(1), &block_id));
+
+    // a block that the inbox dropped is requested from the next peer that announces it
+    gossip.dropped(&block_id);
+    assert!(gossip.announced(PeerID(1), &block_id));
+    assert!(!gossip.announced(PeerID(2), &block_id));
+}
+
+#[test]
+fn test_expired_requests_are_sent_to_other_holders() {
+    let gossip = Gossip::new(8, 100_000, Duration::from_millis(20));
+    let block_id = Id::from([1; 32]);
+
+    assert!(gossip.announced(PeerID(0), &block_id));
+    assert!(!gossip.announced(PeerID(1), &block_id));
+    assert!(!gossip.announced(PeerID(2), &block_id));
+    assert!(gossip.expired().is_empty());
+
+    // unanswered requests go to the holders that were not asked yet
+    std::thread::sleep(Duration::from_millis(30));
+    assert_eq!(gossip.expired(), vec![(PeerID(1), block_id.clone())]);
+    assert!(gossip.expired().is_empty());
+
+    std::thread::sleep(Duration::from_millis(30));
+    assert_eq!(gossip.expired(), vec![(PeerID(2), block_id.clone())]);
+
+    // once every holder was asked, they are asked again in turn
+    std::thread::sleep(Duration::from_millis(30));
+    assert_eq!(gossip.expired(), vec![(PeerID(0), block_id.clone())]);
+
+    // received blocks are no longer requested
+    gossip.received(PeerID(0), &block_id);
+    std::thread::sleep(Duration::from_millis(30));
+    assert!(gossip.expired().is_empty());
+}
+
+#[test]
+fn test_expired_requests_wait_for_another_holder() {
+    let gossip = Gossip::new(8, 100_000, Duration::from_millis(20));
+    let block_id = Id::from([1; 32]);
+
+    assert!(gossip.announced(PeerID(0), &block_id));
+    std::thread::sleep(Duration::from_millis(30));
+    assert!(gossip.expired().is_empty());
+
+    // the next announcement is answered with a request right away
+    assert!(gossip.announced(PeerID(1), &block_id));
+}
@@ -0,0 +1,5 @@
+use virtual_voting::VirtualVotingConfig;
+
+pub trait PruningConfig: VirtualVotingConfig {
+    fn pruning_depth(&self) -> u64;
+}
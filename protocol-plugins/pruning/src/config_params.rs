@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PruningConfigParams {
+    /// The number of accepted heights below the latest confirmed milestone that are kept.
+    pub depth: u64,
+}
+
+impl Default for PruningConfigParams {
+    fn default() -> Self {
+        Self { depth: 32 }
+    }
+}
@@ -0,0 +1,5 @@
+mod config;
+mod config_params;
+mod pruning;
+
+pub use crate::{config::*, config_params::*, pruning::*};
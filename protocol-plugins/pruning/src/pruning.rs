@@ -0,0 +1,135 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, Weak},
+};
+
+use async_trait::async_trait;
+use block_storage::BlockStorage;
+use common::{
+    ids::BlockID,
+    rx::{Callbacks, Subscription},
+    up, with,
+};
+use consensus::{AcceptedBlocks, Consensus, Reorg};
+use protocol::{ManagedPlugin, Plugins};
+use tracing::{Span, debug, info_span, warn};
+use virtual_voting::Vote;
+
+use crate::PruningConfig;
+
+/// Evicts accepted blocks that are more than the configured depth below the latest confirmed
+/// milestone from the [`BlockStorage`].
+pub struct Pruning<C: PruningConfig> {
+    accepted_blocks: Mutex<BTreeMap<u64, Vec<BlockID>>>,
+    block_storage: Arc<BlockStorage>,
+    config: Arc<C>,
+    subscriptions: Mutex<Option<Subscriptions<C>>>,
+    span: Span,
+}
+
+#[async_trait]
+impl<C: PruningConfig> ManagedPlugin for Pruning<C> {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        Arc::new_cyclic(|this: &Weak<Self>| {
+            let consensus = plugins.load::<Consensus<C>>();
+
+            Self {
+                accepted_blocks: Default::default(),
+                block_storage: plugins.load(),
+                config: plugins.get().unwrap(),
+                subscriptions: Mutex::new(Some(Subscriptions {
+                    accepted_blocks: consensus
+                        .accepted_blocks
+                        .subscribe(with!(this: move |accepted| up!(this: this.track(accepted)))),
+                    reorgs: consensus
+                        .reorgs
+                        .subscribe(with!(this: move |reorg| up!(this: {
+                            this.track_reorg(reorg)
+                        }))),
+                    latest_accepted_milestone: consensus.latest_accepted_milestone.subscribe(
+                        with!(this: move |(_, new)| up!(this: {
+                            if let Some(new) = new {
+                                this.prune(new)
+                            }
+                        })),
+                    ),
+                })),
+                span: info_span!("pruning"),
+            }
+        })
+    }
+
+    async fn shutdown(&self) {
+        self.subscriptions.lock().unwrap().take();
+    }
+
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+impl<C: PruningConfig> Pruning<C> {
+    fn track(&self, accepted: &AcceptedBlocks) {
+        let mut accepted_blocks = self.accepted_blocks.lock().unwrap();
+        for (index, round) in accepted.rounds.iter().enumerate() {
+            accepted_blocks
+                .entry(accepted.height + index as u64 + 1)
+                .or_default()
+                .extend(round.iter().map(|block| block.block.id().clone()));
+        }
+    }
+
+    fn track_reorg(&self, reorg: &Reorg) {
+        self.accepted_blocks
+            .lock()
+            .unwrap()
+            .split_off(&(reorg.reverted.height + 1));
+
+        self.track(&reorg.accepted);
+    }
+
+    fn prune(&self, accepted_milestone: &Vote<C>) {
+        let confirmed_height = accepted_milestone
+            .confirmed_milestone()
+            .and_then(Vote::try_from)
+            .and_then(|confirmed| confirmed.height());
+
+        let confirmed_height = match confirmed_height {
+            Ok(height) => height,
+            Err(err) => {
+                self.span
+                    .in_scope(|| warn!(%err, "failed to determine confirmed height"));
+                return;
+            }
+        };
+
+        let Some(prune_below) = confirmed_height.checked_sub(self.config.pruning_depth()) else {
+            return;
+        };
+
+        let pruned = {
+            let mut accepted_blocks = self.accepted_blocks.lock().unwrap();
+            let retained = accepted_blocks.split_off(&prune_below);
+            std::mem::replace(&mut *accepted_blocks, retained)
+        };
+
+        if !pruned.is_empty() {
+            let mut count = 0;
+            for block_id in pruned.into_values().flatten() {
+                count += self.block_storage.evict(&block_id).is_some() as usize;
+            }
+
+            self.span
+                .in_scope(|| debug!(count, below = prune_below, "pruned blocks"));
+        }
+    }
+}
+
+#[allow(dead_code)] // Subscriptions are only held to keep them alive (they act as guards)
+struct Subscriptions<C: PruningConfig> {
+    accepted_blocks: Subscription<Callbacks<AcceptedBlocks>>,
+    reorgs: Subscription<Callbacks<Reorg>>,
+    latest_accepted_milestone: VoteSubscription<C>,
+}
+
+type VoteSubscription<C> = Subscription<Callbacks<(Option<Vote<C>>, Option<Vote<C>>)>>;
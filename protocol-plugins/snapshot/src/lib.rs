@@ -0,0 +1,4 @@
+mod snapshot;
+mod snapshot_block_store;
+
+pub use crate::{snapshot::*, snapshot_block_store::*};
@@ -0,0 +1,188 @@
+use std::{
+    backtrace::Backtrace,
+    collections::HashSet,
+    fs,
+    io::Write,
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use block_storage::BlockStorage;
+use common::{
+    blocks::Block,
+    codec,
+    errors::{Error, Result},
+    ids::BlockID,
+};
+use consensus::{Consensus, ConsensusMetadata};
+use stake_registry::{StakeRegistry, StakeRegistryConfig};
+use virtual_voting::{Checkpoint, Vote};
+
+/// Distinguishes the temporary files of concurrent writes within a process.
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// The state a node needs to resume without replaying the DAG from genesis.
+///
+/// The latest confirmed milestone replaces the genesis block when bootstrapping. Blocks that are
+/// not yet final are stored in full and replayed on top of it, which rebuilds their votes from the
+/// confirmed milestone (so the node may settle on a different unfinalized chain than the one it
+/// had accepted before).
+pub struct Snapshot {
+    /// The latest confirmed milestone.
+    pub confirmed: Checkpoint,
+    /// The latest accepted milestone.
+    pub accepted: Checkpoint,
+    /// The stake registry as of the latest confirmed milestone.
+    pub registry: StakeRegistry,
+    /// Final blocks (other than the checkpoint) that are referenced by the unfinalized blocks.
+    pub anchors: Vec<BlockID>,
+    /// The blocks that are not yet final, ordered by issuing time.
+    pub blocks: Vec<Block>,
+}
+
+impl Snapshot {
+    pub const VERSION: u8 = 2;
+
+    /// Captures the current state of the given node.
+    pub fn take<C: StakeRegistryConfig>(
+        config: &C,
+        consensus: &Consensus<C>,
+        block_storage: &BlockStorage,
+    ) -> virtual_voting::Result<Self> {
+        let accepted = consensus
+            .latest_accepted_milestone
+            .get()
+            .clone()
+            .ok_or(virtual_voting::Error::NoMilestone)?;
+        let confirmed_vote = Vote::try_from(accepted.confirmed_milestone()?)?;
+        let registry = StakeRegistry::at(&confirmed_vote, || config.root_stake_registry())?;
+        let confirmed = Checkpoint::from_vote(&confirmed_vote)?;
+
+        let mut blocks = Vec::new();
+        for block in block_storage.blocks() {
+            let is_final = block
+                .try_get::<Arc<ConsensusMetadata>>()
+                .is_ok_and(|m| m.accepted_height().is_some_and(|h| h <= confirmed.height));
+
+            // roots (the genesis block or the anchors of a snapshot) are only known locally and
+            // are recreated from the anchors when bootstrapping
+            let is_root = matches!(block.block, Block::GenesisBlock(_));
+            if !is_final && !is_root && block.block.id() != &confirmed.block_id {
+                blocks.push(block.block.clone());
+            }
+        }
+        blocks.sort_by(|a, b| (issuing_time(a), a.id()).cmp(&(issuing_time(b), b.id())));
+
+        let ids: HashSet<&BlockID> = blocks.iter().map(|block| block.id()).collect();
+        let mut anchors = Vec::new();
+        for parent in blocks.iter().flat_map(|block| block.parents()) {
+            if !ids.contains(parent) && parent != &confirmed.block_id && !anchors.contains(parent) {
+                anchors.push(parent.clone());
+            }
+        }
+
+        Ok(Self {
+            accepted: Checkpoint::from_vote(&accepted)?,
+            confirmed,
+            registry: (*registry).clone(),
+            anchors,
+            blocks,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        codec::encode(Self::VERSION, &serialization::WireSnapshot::from(self))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let wire: serialization::WireSnapshot = codec::decode(Self::VERSION, bytes)?;
+
+        Ok(Self {
+            confirmed: wire.confirmed,
+            accepted: wire.accepted,
+            registry: wire.registry,
+            anchors: wire.anchors,
+            blocks: wire
+                .blocks
+                .iter()
+                .map(|bytes| Block::from_bytes(bytes))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Writes the snapshot to a temporary file first and renames it afterward, so that a crash
+    /// never leaves a partially written snapshot behind. The temporary file is unique to the
+    /// write, so concurrent writes never interleave.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(format!(
+            ".{}-{}.tmp",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let tmp_path = path.with_file_name(tmp_name);
+
+        let mut file = fs::File::create(&tmp_path).map_err(storage_failed)?;
+        file.write_all(&self.to_bytes()).map_err(storage_failed)?;
+        file.sync_all().map_err(storage_failed)?;
+
+        fs::rename(&tmp_path, path).map_err(storage_failed)
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_bytes(&fs::read(path).map_err(storage_failed)?)
+    }
+}
+
+fn issuing_time(block: &Block) -> u64 {
+    match block {
+        Block::GenesisBlock(_) => 0,
+        Block::NetworkBlock(_, network_block) => network_block.issuing_time,
+    }
+}
+
+fn storage_failed(err: std::io::Error) -> Error {
+    Error::StorageFailed {
+        reason: err.to_string(),
+        backtrace: Backtrace::capture(),
+    }
+}
+
+mod serialization {
+    use common::ids::BlockID;
+    use serde::{Deserialize, Serialize};
+    use stake_registry::StakeRegistry;
+    use virtual_voting::Checkpoint;
+
+    use crate::Snapshot;
+
+    /// Wire representation of a [`Snapshot`] that carries blocks in their encoded form.
+    #[derive(Deserialize, Serialize)]
+    pub(super) struct WireSnapshot {
+        pub confirmed: Checkpoint,
+        pub accepted: Checkpoint,
+        pub registry: StakeRegistry,
+        pub anchors: Vec<BlockID>,
+        pub blocks: Vec<Vec<u8>>,
+    }
+
+    impl From<&Snapshot> for WireSnapshot {
+        fn from(snapshot: &Snapshot) -> Self {
+            Self {
+                confirmed: snapshot.confirmed.clone(),
+                accepted: snapshot.accepted.clone(),
+                registry: snapshot.registry.clone(),
+                anchors: snapshot.anchors.clone(),
+                blocks: snapshot
+                    .blocks
+                    .iter()
+                    .map(|block| block.to_bytes())
+                    .collect(),
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use block_storage::BlockStore;
+use common::{
+    blocks::{Block, Block::GenesisBlock},
+    errors::Result,
+    ids::BlockID,
+};
+
+use crate::Snapshot;
+
+/// A [`BlockStore`] that replays a [`Snapshot`] before the blocks of an optional underlying store.
+///
+/// The confirmed milestone and the anchors are replayed as root blocks (without parents), followed by the
+/// unfinalized blocks of the snapshot. Newly inserted blocks are forwarded to the underlying store.
+pub struct SnapshotBlockStore {
+    snapshot: Arc<Snapshot>,
+    store: Option<Arc<dyn BlockStore>>,
+}
+
+impl SnapshotBlockStore {
+    pub fn new(snapshot: Arc<Snapshot>, store: Option<Arc<dyn BlockStore>>) -> Self {
+        Self { snapshot, store }
+    }
+}
+
+impl BlockStore for SnapshotBlockStore {
+    fn insert(&self, block: &Block) -> Result<()> {
+        match &self.store {
+            Some(store) => store.insert(block),
+            None => Ok(()),
+        }
+    }
+
+    fn blocks(&self) -> Result<Vec<Block>> {
+        let mut blocks = vec![GenesisBlock(self.snapshot.confirmed.block_id.clone())];
+        blocks.extend(self.snapshot.anchors.iter().cloned().map(GenesisBlock));
+        blocks.extend(self.snapshot.blocks.iter().cloned());
+
+        if let Some(store) = &self.store {
+            blocks.extend(store.blocks()?);
+        }
+
+        Ok(blocks)
+    }
+
+    fn accept_milestones(&self, height: u64, milestones: &[BlockID]) -> Result<()> {
+        match &self.store {
+            Some(store) => store.accept_milestones(height, milestones),
+            None => Ok(()),
+        }
+    }
+
+    fn accepted_milestones(&self) -> Result<Vec<(u64, BlockID)>> {
+        match &self.store {
+            Some(store) => store.accepted_milestones(),
+            None => Ok(Vec::new()),
+        }
+    }
+}
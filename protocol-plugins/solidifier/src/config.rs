@@ -0,0 +1,11 @@
+use std::time::Duration;
+
+use networking::NetworkingConfig;
+
+pub trait SolidifierConfig: NetworkingConfig {
+    fn solidifier_check_interval(&self) -> Duration;
+
+    fn solidifier_request_timeout(&self) -> Duration;
+
+    fn solidifier_max_attempts(&self) -> u32;
+}
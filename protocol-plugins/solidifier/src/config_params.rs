@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SolidifierConfigParams {
+    /// The interval (in milliseconds) at which missing blocks are checked. Blocks that are still
+    /// missing one interval after they were first noticed are requested from the peers.
+    pub check_interval: u64,
+    /// The time (in milliseconds) to wait for a response before a request is retried.
+    pub request_timeout: u64,
+    /// The number of requests that are sent for a missing block before giving up on it.
+    pub max_attempts: u32,
+}
+
+impl Default for SolidifierConfigParams {
+    fn default() -> Self {
+        Self {
+            check_interval: 50,
+            request_timeout: 500,
+            max_attempts: 5,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod config;
+mod config_params;
+mod solidifier;
+
+pub use crate::{config::*, config_params::*, solidifier::*};
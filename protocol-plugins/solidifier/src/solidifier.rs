@@ -0,0 +1,311 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use block_storage::{Address, BlockStorage};
+use common::{
+    blocks::{Block, BlockMetadata},
+    ids::BlockID,
+    networking::PeerID,
+    rx::{Callbacks, Event, Subscription},
+    traced::{self, WorkerFailure},
+    up, with,
+};
+use networking::Networking;
+use protocol::{ManagedPlugin, Plugins};
+use tokio::{
+    sync::{Notify, watch},
+    task::JoinHandle,
+    time::{Instant, MissedTickBehavior},
+};
+use tracing::{Level, Span, debug, info_span, span, trace, warn};
+
+use crate::SolidifierConfig;
+
+/// Requests blocks that are referenced but missing from the [`BlockStorage`] (i.e. whose address
+/// is allocated but still empty) from the peers.
+///
+/// Missing blocks are tracked as their addresses are allocated and filled. Blocks that are still
+/// missing one check interval after they were first noticed are requested over [`Networking`].
+/// Missing parents of requested blocks are requested as soon as the requested block arrives, so
+/// that a node can catch up with a past cone faster than it grows. Unanswered requests are retried
+/// after the configured timeout until the maximum number of attempts is reached. Blocks that were
+/// given up on are requested again when they are announced or a new peer connects.
+pub struct Solidifier<C: SolidifierConfig> {
+    requests: Arc<Mutex<Requests>>,
+    parents_arrived: Arc<Notify>,
+    block_storage: Arc<BlockStorage>,
+    networking: Arc<Networking<C>>,
+    config: Arc<C>,
+    subscriptions: Mutex<Option<Subscriptions>>,
+    worker: tokio::sync::Mutex<Option<Worker>>,
+    worker_failures: Event<WorkerFailure>,
+    span: Span,
+}
+
+#[async_trait]
+impl<C: SolidifierConfig> ManagedPlugin for Solidifier<C> {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        Arc::new_cyclic(|this: &Weak<Self>| {
+            let block_storage = plugins.load::<BlockStorage>();
+            let networking = plugins.load::<Networking<C>>();
+            let config = plugins.get::<C>().unwrap();
+
+            Self {
+                requests: Arc::new(Mutex::new(Requests::new(
+                    config.solidifier_check_interval(),
+                    config.solidifier_request_timeout(),
+                    config.solidifier_max_attempts(),
+                ))),
+                parents_arrived: Default::default(),
+                subscriptions: Mutex::new(Some(Subscriptions {
+                    new_address: block_storage.new_address.subscribe(
+                        with!(this: move |(block_id, address)| up!(this: {
+                            this.track_address(block_id, address)
+                        })),
+                    ),
+                    block_announcements: networking.dispatcher.block_announcements.subscribe(
+                        with!(this: move |(_, block_id)| up!(this: {
+                            this.requests.lock().unwrap().rearm(block_id)
+                        })),
+                    ),
+                    peers: networking
+                        .peers
+                        .subscribe(with!(this: move |(old, new)| up!(this: {
+                            let old = old.as_deref().unwrap_or_default();
+                            if new.iter().flatten().any(|peer| !old.contains(peer)) {
+                                this.requests.lock().unwrap().rearm_all()
+                            }
+                        }))),
+                })),
+                block_storage,
+                networking,
+                config,
+                worker: tokio::sync::Mutex::new(None),
+                worker_failures: plugins.worker_failures(),
+                span: info_span!("solidifier"),
+            }
+        })
+    }
+
+    async fn start(&self) {
+        let (shutdown_signal, is_shutdown) = watch::channel(());
+        *self.worker.lock().await = Some((self.worker(is_shutdown), shutdown_signal));
+    }
+
+    async fn shutdown(&self) {
+        self.subscriptions.lock().unwrap().take();
+        if let Some((worker, shutdown)) = self.worker.lock().await.take() {
+            drop(shutdown); // close the shutdown channel to signal the worker to stop
+            let _ = worker.await;
+        }
+    }
+
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+impl<C: SolidifierConfig> Solidifier<C> {
+    fn worker(&self, mut is_shutdown: watch::Receiver<()>) -> JoinHandle<()> {
+        let requests = self.requests.clone();
+        let parents_arrived = self.parents_arrived.clone();
+        let block_storage = self.block_storage.clone();
+        let networking = self.networking.clone();
+        let mut interval = tokio::time::interval(self.config.solidifier_check_interval());
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        traced::worker(
+            async move {
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            let to_request = requests.lock().unwrap().update(Instant::now());
+                            for block_id in to_request {
+                                networking.request_block(&block_id);
+                            }
+                        },
+                        _ = parents_arrived.notified() => {
+                            let parents = requests.lock().unwrap().take_parents();
+                            for parent_id in parents {
+                                if block_storage.get(&parent_id).is_none()
+                                    && requests.lock().unwrap().request(&parent_id, Instant::now())
+                                {
+                                    networking.request_block(&parent_id);
+                                }
+                            }
+                        },
+                        _ = is_shutdown.changed() => break, // channel closed = shutdown
+                    }
+                }
+            },
+            span!(parent: self.span.clone(), Level::INFO, "worker"),
+            &self.worker_failures,
+        )
+    }
+
+    /// Tracks a newly allocated address as missing until its block is stored.
+    fn track_address(self: Arc<Self>, block_id: &BlockID, address: &Address) {
+        self.requests
+            .lock()
+            .unwrap()
+            .missing(block_id.clone(), Instant::now());
+
+        let this = Arc::downgrade(&self);
+        address.attach(with!(this: move |block| up!(this: this.track_parents(block))));
+    }
+
+    /// Hands the parents of a requested block to the worker, which requests the missing ones.
+    /// This runs while the address of the block is locked, so it must not access other addresses.
+    fn track_parents(&self, block: &BlockMetadata) {
+        if self.requests.lock().unwrap().arrived(&block.block) {
+            self.parents_arrived.notify_one();
+        }
+    }
+}
+
+/// Keeps track of the requests for missing blocks.
+struct Requests {
+    pending: HashMap<BlockID, Request>,
+    parents: Vec<BlockID>,
+    check_interval: Duration,
+    timeout: Duration,
+    max_attempts: u32,
+}
+
+struct Request {
+    attempts: u32,
+    /// When the block was noticed as missing or last requested. Blocks without it are requested
+    /// on the next check.
+    since: Option<Instant>,
+    given_up: bool,
+}
+
+impl Requests {
+    fn new(check_interval: Duration, timeout: Duration, max_attempts: u32) -> Self {
+        Self {
+            pending: HashMap::new(),
+            parents: Vec::new(),
+            check_interval,
+            timeout,
+            max_attempts,
+        }
+    }
+
+    /// Tracks the given block as missing, unless it is already tracked.
+    fn missing(&mut self, block_id: BlockID, now: Instant) {
+        self.pending.entry(block_id).or_insert(Request {
+            attempts: 0,
+            since: Some(now),
+            given_up: false,
+        });
+    }
+
+    /// Stops tracking the given block. If it had been requested, its parents are queued to be
+    /// checked and `true` is returned.
+    fn arrived(&mut self, block: &Block) -> bool {
+        let requested = self
+            .pending
+            .remove(block.id())
+            .is_some_and(|request| request.attempts > 0);
+        if requested {
+            trace!(block_id = %block.id(), "requested block arrived");
+            self.parents.extend_from_slice(block.parents());
+        }
+
+        requested
+    }
+
+    fn take_parents(&mut self) -> Vec<BlockID> {
+        std::mem::take(&mut self.parents)
+    }
+
+    /// Tracks the given block as requested now, unless it has already been requested. Returns
+    /// whether it should be requested.
+    fn request(&mut self, block_id: &BlockID, now: Instant) -> bool {
+        let request = self.pending.entry(block_id.clone()).or_insert(Request {
+            attempts: 0,
+            since: None,
+            given_up: false,
+        });
+        if request.attempts > 0 {
+            return false;
+        }
+
+        request.attempts = 1;
+        request.since = Some(now);
+        debug!(%block_id, attempt = 1, "requesting missing parent");
+        true
+    }
+
+    /// Requests the given block again on the next check if it was given up on.
+    fn rearm(&mut self, block_id: &BlockID) {
+        if let Some(request) = self.pending.get_mut(block_id) {
+            Self::rearm_request(block_id, request);
+        }
+    }
+
+    /// Requests all blocks that were given up on again on the next check.
+    fn rearm_all(&mut self) {
+        for (block_id, request) in self.pending.iter_mut() {
+            Self::rearm_request(block_id, request);
+        }
+    }
+
+    fn rearm_request(block_id: &BlockID, request: &mut Request) {
+        if request.given_up {
+            debug!(%block_id, "requesting given up block again");
+            *request = Request {
+                attempts: 0,
+                since: None,
+                given_up: false,
+            };
+        }
+    }
+
+    /// Returns the missing blocks that should be requested now.
+    fn update(&mut self, now: Instant) -> Vec<BlockID> {
+        let mut to_request = Vec::new();
+        for (block_id, request) in self.pending.iter_mut() {
+            if request.given_up {
+                continue;
+            }
+
+            if request.attempts >= self.max_attempts {
+                request.given_up = true;
+                warn!(%block_id, attempts = request.attempts, "giving up on missing block");
+                continue;
+            }
+
+            // give the block one interval to arrive on its own before requesting it
+            let wait = match request.attempts {
+                0 => self.check_interval,
+                _ => self.timeout,
+            };
+            if request
+                .since
+                .is_none_or(|since| now.duration_since(since) >= wait)
+            {
+                request.attempts += 1;
+                request.since = Some(now);
+                debug!(%block_id, attempt = request.attempts, "requesting missing block");
+                to_request.push(block_id.clone());
+            }
+        }
+
+        to_request
+    }
+}
+
+#[allow(dead_code)] // Subscriptions are only held to keep them alive (they act as guards)
+struct Subscriptions {
+    new_address: Subscription<Callbacks<(BlockID, Address)>>,
+    block_announcements: Subscription<Callbacks<(PeerID, BlockID)>>,
+    peers: PeersSubscription,
+}
+
+type Worker = (JoinHandle<()>, watch::Sender<()>);
+type PeersSubscription = Subscription<Callbacks<(Option<Vec<PeerID>>, Option<Vec<PeerID>>)>>;
@@ -0,0 +1,8 @@
+use virtual_voting::VirtualVotingConfig;
+
+use crate::StakeRegistry;
+
+pub trait StakeRegistryConfig: VirtualVotingConfig {
+    /// The registry of the root milestone (the genesis block or the checkpoint of a snapshot).
+    fn root_stake_registry(&self) -> StakeRegistry;
+}
@@ -0,0 +1,6 @@
+mod config;
+mod registry_record;
+mod stake_registry;
+mod stake_tracker;
+
+pub use crate::{config::*, registry_record::*, stake_registry::*, stake_tracker::*};
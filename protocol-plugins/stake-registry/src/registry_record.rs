@@ -0,0 +1,22 @@
+use common::{codec, errors::Result};
+use serde::{Deserialize, Serialize};
+
+/// A change to the stake registry, carried as the payload of a block and applied to the issuer
+/// of that block.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RegistryRecord {
+    Register { stake: u64 },
+    Deregister,
+}
+
+impl RegistryRecord {
+    pub const VERSION: u8 = 1;
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        codec::encode(Self::VERSION, self)
+    }
+
+    pub fn from_payload(payload: &[u8]) -> Result<Self> {
+        codec::decode(Self::VERSION, payload)
+    }
+}
@@ -0,0 +1,97 @@
+use std::{cmp::Reverse, collections::BTreeMap, sync::Arc};
+
+use common::{
+    bft::{Committee, Member},
+    blocks::{Block, BlockMetadata},
+    ids::IssuerID,
+};
+use serde::{Deserialize, Serialize};
+use virtual_voting::{VirtualVotingConfig, Vote};
+
+use crate::RegistryRecord;
+
+/// The declared stake of every registered validator.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StakeRegistry {
+    stakes: BTreeMap<IssuerID, u64>,
+}
+
+impl StakeRegistry {
+    /// Seeds the registry with the members of a committee, using their weights as stake.
+    pub fn from_committee(committee: &Committee) -> Self {
+        Self {
+            stakes: committee
+                .iter()
+                .map(|member| (member.id().clone(), member.weight()))
+                .collect(),
+        }
+    }
+
+    /// Returns the registry as of the given milestone.
+    ///
+    /// The registry of a milestone is tracked by the [`StakeTracker`](crate::StakeTracker) once
+    /// the milestone got accepted, so it only depends on the accepted history. Root milestones
+    /// use the given root registry instead.
+    pub fn at<C: VirtualVotingConfig>(
+        milestone: &Vote<C>,
+        root: impl FnOnce() -> StakeRegistry,
+    ) -> virtual_voting::Result<Arc<Self>> {
+        if milestone.prev_milestone()?.points_to(milestone) {
+            return Ok(Arc::new(root()));
+        }
+
+        Ok(milestone.source.try_upgrade()?.try_get::<Arc<Self>>()?)
+    }
+
+    /// Applies the registry records of the given blocks in the order of their issuing time (ties
+    /// broken by block id), so the result does not depend on the order in which a node received
+    /// the blocks.
+    pub fn apply_blocks<'a>(&mut self, blocks: impl IntoIterator<Item = &'a BlockMetadata>) {
+        let mut records = Vec::new();
+        for block in blocks {
+            if let Block::NetworkBlock(id, network_block) = &block.block
+                && let Ok(record) = RegistryRecord::from_payload(&network_block.payload)
+            {
+                records.push((
+                    (network_block.issuing_time, id.clone()),
+                    network_block.issuer_id.clone(),
+                    record,
+                ));
+            }
+        }
+        records.sort_by(|(a, ..), (b, ..)| a.cmp(b));
+
+        for (_, issuer, record) in records {
+            self.apply(&issuer, &record);
+        }
+    }
+
+    pub fn apply(&mut self, issuer: &IssuerID, record: &RegistryRecord) {
+        match record {
+            RegistryRecord::Register { stake } => self.stakes.insert(issuer.clone(), *stake),
+            RegistryRecord::Deregister => self.stakes.remove(issuer),
+        };
+    }
+
+    pub fn stake(&self, issuer: &IssuerID) -> u64 {
+        self.stakes.get(issuer).copied().unwrap_or(0)
+    }
+
+    /// Builds a committee of the `max_size` validators with the highest stake (ties are broken by
+    /// the lower `IssuerID`).
+    pub fn committee(&self, max_size: usize) -> Committee {
+        let mut candidates: Vec<_> = self
+            .stakes
+            .iter()
+            .filter(|(_, stake)| **stake > 0)
+            .collect();
+        candidates.sort_by_key(|(issuer, stake)| (Reverse(**stake), *issuer));
+
+        Committee::from(
+            candidates
+                .into_iter()
+                .take(max_size)
+                .map(|(issuer, stake)| Member::new(issuer.clone()).with_weight(*stake)),
+        )
+    }
+}
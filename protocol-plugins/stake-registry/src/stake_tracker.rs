@@ -0,0 +1,90 @@
+use std::sync::{Arc, Mutex, Weak};
+
+use async_trait::async_trait;
+use common::{
+    blocks::BlockMetadata,
+    rx::{Callbacks, Subscription},
+    up, with,
+};
+use consensus::{AcceptedBlocks, Consensus, Reorg};
+use indexmap::IndexSet;
+use protocol::{ManagedPlugin, Plugins};
+use tracing::{Span, error, info_span, trace};
+use virtual_voting::Vote;
+
+use crate::{StakeRegistry, StakeRegistryConfig};
+
+/// Maintains the [`StakeRegistry`] of every accepted milestone.
+///
+/// The registry of a milestone is the one of its previous milestone with the registry records of
+/// the blocks that it accepted applied on top. It is stored in the metadata of the milestone's
+/// block, so that committee selection does not have to replay the accepted history.
+pub struct StakeTracker<C: StakeRegistryConfig> {
+    config: Arc<C>,
+    subscriptions: Mutex<Option<Subscriptions>>,
+    span: Span,
+}
+
+#[async_trait]
+impl<C: StakeRegistryConfig> ManagedPlugin for StakeTracker<C> {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        Arc::new_cyclic(|this: &Weak<Self>| {
+            let consensus = plugins.load::<Consensus<C>>();
+
+            Self {
+                config: plugins.get().unwrap(),
+                subscriptions: Mutex::new(Some(Subscriptions {
+                    accepted_blocks: consensus
+                        .accepted_blocks
+                        .subscribe(with!(this: move |accepted| up!(this: this.track(accepted)))),
+                    reorgs: consensus.reorgs.subscribe(
+                        with!(this: move |reorg| up!(this: this.track(&reorg.accepted))),
+                    ),
+                })),
+                span: info_span!("stake_tracker"),
+            }
+        })
+    }
+
+    async fn shutdown(&self) {
+        self.subscriptions.lock().unwrap().take();
+    }
+
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+impl<C: StakeRegistryConfig> StakeTracker<C> {
+    fn track(&self, accepted: &AcceptedBlocks) {
+        for (index, round) in accepted.rounds.iter().enumerate() {
+            let height = accepted.height + index as u64 + 1;
+            if let Err(err) = self.track_round(round) {
+                error!(height, %err, "failed to track stake registry");
+            }
+        }
+    }
+
+    fn track_round(&self, round: &IndexSet<BlockMetadata>) -> virtual_voting::Result<()> {
+        let Some(milestone) = round.first() else {
+            return Ok(());
+        };
+
+        let vote = milestone.try_get::<Vote<C>>()?;
+        let prev = Vote::try_from(vote.prev_milestone()?)?;
+        let mut registry =
+            (*StakeRegistry::at(&prev, || self.config.root_stake_registry())?).clone();
+        registry.apply_blocks(round);
+
+        trace!(block = %milestone.block.id(), "stake registry tracked");
+        milestone.set(Arc::new(registry));
+
+        Ok(())
+    }
+}
+
+#[allow(dead_code)] // Subscriptions are only held to keep them alive (they act as guards)
+struct Subscriptions {
+    accepted_blocks: Subscription<Callbacks<AcceptedBlocks>>,
+    reorgs: Subscription<Callbacks<Reorg>>,
+}
@@ -0,0 +1,45 @@
+use common::{
+    bft::{Committee, Member},
+    ids::IssuerID,
+};
+use stake_registry::{RegistryRecord, StakeRegistry};
+
+#[test]
+fn test_registry_record_payload() -> common::errors::Result<()> {
+    let record = RegistryRecord::Register { stake: 42 };
+    assert_eq!(RegistryRecord::from_payload(&record.to_payload())?, record);
+    assert!(RegistryRecord::from_payload(b"not a record").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_stake_registry() {
+    let issuer = |n: u8| IssuerID::from([n; 32]);
+    let genesis = Committee::from([
+        Member::new(issuer(1)).with_weight(10),
+        Member::new(issuer(2)).with_weight(10),
+    ]);
+
+    let mut registry = StakeRegistry::from_committee(&genesis);
+    registry.apply(&issuer(3), &RegistryRecord::Register { stake: 20 });
+    registry.apply(&issuer(4), &RegistryRecord::Register { stake: 10 });
+    registry.apply(&issuer(2), &RegistryRecord::Deregister);
+    assert_eq!(registry.stake(&issuer(2)), 0);
+    assert_eq!(registry.stake(&issuer(3)), 20);
+
+    // the highest stake wins and ties are broken by the lower issuer id
+    let committee = registry.committee(2);
+    assert_eq!(committee.size(), 2);
+    assert_eq!(committee.total_weight(), 30);
+    assert!(committee.member(&issuer(3)).is_some());
+    assert!(committee.member(&issuer(1)).is_some());
+    assert!(committee.member(&issuer(4)).is_none());
+
+    // the result does not depend on the order in which equal records were applied
+    let mut reordered = StakeRegistry::default();
+    reordered.apply(&issuer(4), &RegistryRecord::Register { stake: 10 });
+    reordered.apply(&issuer(3), &RegistryRecord::Register { stake: 20 });
+    reordered.apply(&issuer(1), &RegistryRecord::Register { stake: 10 });
+    assert_eq!(reordered.committee(2).commitment(), committee.commitment());
+}
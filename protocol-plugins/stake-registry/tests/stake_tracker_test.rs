@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use block_storage::BlockStorage;
+use common::{
+    bft::{Committee, Member},
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::{BlockID, Id},
+};
+use config::{CommitteeRotation, CommitteeSelection, Config};
+use consensus::Consensus;
+use protocol::Protocol;
+use stake_registry::{RegistryRecord, StakeRegistry};
+
+#[tokio::test]
+async fn test_stake_tracker() {
+    let key = |seed: u8| SigningKey::from([seed; 32]);
+    let genesis = Committee::from((1..=4).map(|seed| Member::new(key(seed).issuer_id())));
+    let protocol = Protocol::new(
+        Config::default()
+            .with_committee_selection(CommitteeSelection::StakeRegistry {
+                genesis,
+                max_size: 4,
+            })
+            .with_committee_rotation(CommitteeRotation::EveryHeights(5)),
+    );
+    let block_storage = protocol.plugins.get::<BlockStorage>().unwrap();
+    let consensus = protocol.plugins.get::<Consensus<Config>>().unwrap();
+    protocol.start().await;
+
+    // validator 5 registers and validator 4 leaves in the first round, then everybody references
+    // all blocks of the previous round
+    let mut parents: Vec<BlockID> = vec![Id::default()];
+    for round in 1..=12 {
+        parents = (1..=5)
+            .map(|seed| {
+                let payload = match (round, seed) {
+                    (1, 5) => RegistryRecord::Register { stake: 1 }.to_payload(),
+                    (1, 4) => RegistryRecord::Deregister.to_payload(),
+                    _ => vec![],
+                };
+                let block = Block::from(NetworkBlock::new(
+                    parents.clone(),
+                    round,
+                    payload,
+                    &key(seed),
+                ));
+                let id = block.id().clone();
+                block_storage.insert(block).expect("block must be new");
+                id
+            })
+            .collect();
+    }
+
+    // the registry of the accepted milestone was tracked on acceptance
+    let accepted = consensus.latest_accepted_milestone.get().clone().unwrap();
+    let registry = accepted
+        .source
+        .try_upgrade()
+        .unwrap()
+        .try_get::<Arc<StakeRegistry>>()
+        .unwrap();
+    assert_eq!(registry.stake(&key(5).issuer_id()), 1);
+    assert_eq!(registry.stake(&key(4).issuer_id()), 0);
+
+    // and the committee rotated to the registered validators
+    let committee = consensus.committee.get().clone().unwrap();
+    assert!(committee.member(&key(4).issuer_id()).is_none());
+    assert_eq!(
+        committee.member(&key(5).issuer_id()).map(Member::weight),
+        Some(1)
+    );
+
+    // milestones that are not accepted yet have no registry instead of a made up one
+    let heaviest = consensus.heaviest_milestone_vote.get().clone().unwrap();
+    assert!(heaviest.height().unwrap() > accepted.height().unwrap());
+    assert!(StakeRegistry::at(&heaviest, StakeRegistry::default).is_err());
+
+    protocol.shutdown().await;
+}
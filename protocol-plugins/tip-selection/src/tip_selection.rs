@@ -84,7 +84,7 @@ impl<C: VirtualVotingConfig> TipSelection<C> {
                     }
                 }
                 Err(err) => {
-                    tips.extend(removed_tips.into_iter());
+                    tips.extend(removed_tips);
 
                     return Err(err);
                 }
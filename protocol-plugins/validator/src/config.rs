@@ -1,20 +1,10 @@
-use common::ids::IssuerID;
-use config::Config;
-use protocol::ProtocolConfig;
-use virtual_voting::VirtualVotingConfig;
+use common::{crypto::SigningKey, ids::IssuerID};
+use inbox::InboxConfig;
 
-use crate::ValidatorConfigParams;
+pub trait ValidatorConfig: InboxConfig {
+    fn validator_key(&self) -> SigningKey;
 
-pub trait ValidatorConfig: VirtualVotingConfig {
-    fn validator_id(&self) -> IssuerID;
-}
-
-impl ValidatorConfig for Config {
     fn validator_id(&self) -> IssuerID {
-        let params = self
-            .params::<ValidatorConfigParams>()
-            .expect("ValidatorConfigParams not found in config");
-
-        params.validator_id.clone()
+        self.validator_key().issuer_id()
     }
 }
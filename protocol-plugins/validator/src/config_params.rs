@@ -1,5 +1,10 @@
-use common::ids::IssuerID;
+use common::crypto::SigningKey;
+use serde::Deserialize;
 
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ValidatorConfigParams {
-    pub validator_id: IssuerID,
+    /// The key that blocks are signed with, given as the hex seed of the key in config files.
+    #[serde(rename = "seed", deserialize_with = "common::codec::deserialize_hex")]
+    pub signing_key: SigningKey,
 }
@@ -23,11 +23,11 @@ impl<C: ValidatorConfig> ManagedPlugin for Validator<C> {
             let config = plugins.get::<C>().expect("Validator config not found");
             let consensus_round = plugins.load::<ConsensusRound<C>>();
             let block_factory = plugins.load::<BlockFactory<C>>();
-            let inbox = plugins.load::<Inbox>();
+            let inbox = plugins.load::<Inbox<C>>();
 
             consensus_round.completed.subscribe(with!(this: down!(config, inbox, block_factory: move |(_, new)| up!(this, config, inbox, block_factory: {
                 this.span.in_scope(|| {
-                    let block = block_factory.create_block(&config.validator_id());
+                    let block = block_factory.create_block(&config.validator_key());
                     info!("issuing block for round {:?} (id={:?})", new.unwrap_or(0), block.id());
                     if let Err(e) = inbox.send(block) {
                         error!("issuing block for round {:?} failed: {e}", new);
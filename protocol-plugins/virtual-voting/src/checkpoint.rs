@@ -0,0 +1,36 @@
+use common::{bft::Committee, ids::BlockID};
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, VirtualVotingConfig, Vote};
+
+/// The state of a milestone that a node can bootstrap from instead of from genesis.
+///
+/// The block of the checkpoint becomes the new root of the vote graph. Its vote is rebuilt from
+/// the recorded state and acts as its own accepted, confirmed and previous milestone (just like
+/// the genesis vote does).
+#[derive(Clone, Deserialize, Serialize)]
+pub struct Checkpoint {
+    pub block_id: BlockID,
+    pub time: u64,
+    pub slot: u64,
+    pub height: u64,
+    pub round: u64,
+    pub epoch: u64,
+    pub cumulative_slot_weight: u64,
+    pub committee: Committee,
+}
+
+impl Checkpoint {
+    pub fn from_vote<C: VirtualVotingConfig>(vote: &Vote<C>) -> Result<Self> {
+        Ok(Self {
+            block_id: vote.source.try_upgrade()?.block.id().clone(),
+            time: vote.time,
+            slot: vote.slot,
+            height: vote.height()?,
+            round: vote.round,
+            epoch: vote.epoch,
+            cumulative_slot_weight: vote.cumulative_slot_weight,
+            committee: vote.committee.clone(),
+        })
+    }
+}
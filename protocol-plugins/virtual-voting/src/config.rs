@@ -1,16 +1,29 @@
 use common::bft::Committee;
 use protocol::ProtocolConfig;
 
-use crate::{Vote, VoteBuilder};
+use crate::{Checkpoint, Result, Vote, VoteBuilder};
 
 pub trait VirtualVotingConfig: ProtocolConfig {
     fn genesis_time(&self) -> u64;
 
+    /// The checkpoint to bootstrap from instead of the genesis block (if any).
+    fn checkpoint(&self) -> Option<Checkpoint>;
+
+    /// Returns the slot of the given time, counted from the genesis time.
     fn slot_oracle(&self, time: u64) -> u64;
 
+    /// Returns the time that the given slot starts at (the inverse of the slot oracle).
+    fn slot_start_time(&self, slot: u64) -> u64;
+
     fn offline_threshold(&self) -> u64;
 
-    fn select_committee(&self, vote: Option<&Vote<Self>>) -> Committee;
+    fn max_time_drift(&self) -> u64;
+
+    /// Selects the committee of the genesis block (if no vote is given) or the committee that
+    /// takes over after the epoch of the given vote.
+    fn select_committee(&self, vote: Option<&Vote<Self>>) -> Result<Committee>;
+
+    fn committee_epoch(&self, vote: &VoteBuilder<Self>) -> u64;
 
     fn leader_weight(&self, vote: &VoteBuilder<Self>) -> u64;
 }
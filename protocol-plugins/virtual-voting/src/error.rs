@@ -21,9 +21,15 @@ pub enum Error {
     #[error("No milestone")]
     NoMilestone,
 
+    #[error("No common milestone")]
+    NoCommonMilestone,
+
     #[error("Time must increase")]
     TimeMustIncrease,
 
+    #[error("Committee must not be empty")]
+    EmptyCommittee,
+
     #[error("Common error: {0}")]
     CommonError(#[from] CommonError),
 }
@@ -1,6 +1,6 @@
 pub use crate::{
-    collections::*, config::*, consensus_mechanism::*, error::*, issuer::*, milestone::*,
-    plugin::*, vote::*, vote_builder::*, vote_ref::*, weight_tracker::*,
+    checkpoint::*, collections::*, config::*, consensus_mechanism::*, error::*, issuer::*,
+    milestone::*, plugin::*, vote::*, vote_builder::*, vote_ref::*, weight_tracker::*,
 };
 pub mod builtin {}
 mod collections {
@@ -16,6 +16,7 @@ mod collections {
     pub use votes_by_issuer::*;
     pub use votes_by_round::*;
 }
+mod checkpoint;
 mod config;
 mod consensus_mechanism;
 mod error;
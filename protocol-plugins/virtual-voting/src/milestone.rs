@@ -1,3 +1,5 @@
+use common::bft::Committee;
+
 use crate::{VirtualVotingConfig, VoteRef};
 
 pub struct Milestone<C: VirtualVotingConfig> {
@@ -7,4 +9,7 @@ pub struct Milestone<C: VirtualVotingConfig> {
     pub confirmed: VoteRef<C>,
     pub prev: VoteRef<C>,
     pub slot_boundary: VoteRef<C>,
+    /// The committee selected for the next epoch, which takes over once this milestone is
+    /// accepted.
+    pub next_committee: Option<(u64, Committee)>,
 }
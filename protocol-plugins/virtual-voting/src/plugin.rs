@@ -1,18 +1,21 @@
 use std::{
+    backtrace::Backtrace,
     marker::PhantomData,
     sync::{Arc, Mutex, Weak},
 };
 
 use async_trait::async_trait;
 use block_dag::{BlockDAG, BlockDAGMetadata};
+use block_storage::BlockStorage;
 use common::{
-    blocks::{Block, BlockMetadata, BlockMetadataRef},
+    blocks::{Block, BlockMetadata, NetworkBlock},
+    errors::Error as CommonError,
     rx::{Callbacks, Subscription},
 };
 use protocol::{ManagedPlugin, Plugins};
-use tracing::{Span, info_span};
+use tracing::{Span, debug, info_span, warn};
 
-use crate::{Result, VirtualVotingConfig, Vote, Votes};
+use crate::{Checkpoint, Result, VirtualVotingConfig, Vote, Votes};
 
 pub struct VirtualVoting<C: VirtualVotingConfig> {
     subscription: Mutex<Option<Subscription<Callbacks<BlockMetadata>>>>,
@@ -29,6 +32,27 @@ impl<C: VirtualVotingConfig> VirtualVoting<C> {
 
         Ok(result)
     }
+
+    fn checkpoint_vote(block_storage: &BlockStorage, checkpoint: &Checkpoint) -> Result<Vote<C>> {
+        let block =
+            block_storage
+                .get(&checkpoint.block_id)
+                .ok_or_else(|| CommonError::BlockNotFound {
+                    block_id: checkpoint.block_id.clone(),
+                    backtrace: Backtrace::capture(),
+                })?;
+
+        Ok(block.try_get::<Vote<C>>()?)
+    }
+
+    fn create_vote(block: &BlockMetadata, network_block: &NetworkBlock) -> Result<Vote<C>> {
+        Vote::new(
+            block.downgrade(),
+            &network_block.issuer_id,
+            network_block.issuing_time,
+            Self::referenced_votes(block)?,
+        )
+    }
 }
 
 #[async_trait]
@@ -36,40 +60,55 @@ impl<C: VirtualVotingConfig> ManagedPlugin for VirtualVoting<C> {
     fn new(plugins: &mut Plugins) -> Arc<Self> {
         Arc::new_cyclic(|_virtual_voting: &Weak<Self>| {
             let block_dag: Arc<BlockDAG> = plugins.load();
+            let block_storage: Arc<BlockStorage> = plugins.load();
             let config: Arc<C> = plugins.get().unwrap();
+            let span = info_span!("virtual_voting");
 
             Self {
-                subscription: Mutex::new(Some(block_dag.block_available.subscribe(move |block| {
-                    match &block.block {
-                        Block::NetworkBlock(_, network_block) => {
-                            let src: BlockMetadataRef = block.downgrade();
-                            match Self::referenced_votes(block) {
-                                Ok(referenced_votes) => match Vote::new(
-                                    src,
-                                    &network_block.issuer_id,
-                                    0,
-                                    referenced_votes,
-                                ) {
+                subscription: Mutex::new(Some(block_dag.block_available.subscribe({
+                    let span = span.clone();
+                    move |block| match &block.block {
+                        Block::NetworkBlock(id, network_block) => {
+                            match Self::create_vote(block, network_block) {
+                                Ok(vote) => {
+                                    block.metadata().set(vote);
+                                }
+                                Err(err) => {
+                                    span.in_scope(|| warn!(block = %id, %err, "vote rejected"));
+                                }
+                            }
+                        }
+                        Block::GenesisBlock(id) => match config.checkpoint() {
+                            None => {
+                                block
+                                    .metadata()
+                                    .set(Vote::new_genesis(block.downgrade(), config.clone()));
+                            }
+                            Some(checkpoint) if checkpoint.block_id == *id => {
+                                block.metadata().set(Vote::from_checkpoint(
+                                    block.downgrade(),
+                                    config.clone(),
+                                    &checkpoint,
+                                ));
+                            }
+                            Some(checkpoint) => {
+                                match Self::checkpoint_vote(&block_storage, &checkpoint) {
                                     Ok(vote) => {
-                                        block.metadata().set(vote);
+                                        block
+                                            .metadata()
+                                            .set(Vote::new_anchor(block.downgrade(), &vote));
                                     }
                                     Err(err) => {
-                                        println!("Error creating vote1: {:?}", err);
+                                        span.in_scope(
+                                            || debug!(block = %id, %err, "anchor skipped"),
+                                        );
                                     }
-                                },
-                                Err(err) => {
-                                    println!("Error creating vote2: {:?}", err);
                                 }
                             }
-                        }
-                        _ => {
-                            block
-                                .metadata()
-                                .set(Vote::new_genesis(block.downgrade(), config.clone()));
-                        }
-                    };
+                        },
+                    }
                 }))),
-                span: info_span!("virtual_voting"),
+                span,
                 _marker: PhantomData,
             }
         })
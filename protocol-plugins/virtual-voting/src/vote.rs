@@ -1,10 +1,12 @@
-use std::sync::Arc;
+use std::{cmp::Ordering, sync::Arc};
 
 use common::{blocks::BlockMetadataRef, ids::IssuerID};
 use zero::{Clone0, Deref0};
 
 use crate::{
-    Error::NoMilestone, Milestone, Result, VirtualVotingConfig, VoteBuilder, VoteRef, Votes,
+    Checkpoint,
+    Error::{NoCommonMilestone, NoMilestone},
+    Milestone, Result, VirtualVotingConfig, VoteBuilder, VoteRef, Votes,
 };
 
 #[derive(Clone0, Deref0)]
@@ -24,6 +26,25 @@ impl<C: VirtualVotingConfig> Vote<C> {
         VoteBuilder::build_genesis(source, config)
     }
 
+    pub fn from_checkpoint(
+        source: BlockMetadataRef,
+        config: Arc<C>,
+        checkpoint: &Checkpoint,
+    ) -> Self {
+        VoteBuilder::build_checkpoint(source, config, checkpoint)
+    }
+
+    /// Creates the vote of a block below the checkpoint that is referenced by blocks above it. It
+    /// carries no milestone and inherits the perception of the checkpoint vote.
+    pub fn new_anchor(source: BlockMetadataRef, checkpoint_vote: &Vote<C>) -> Self {
+        VoteBuilder::build_anchor(source, checkpoint_vote)
+    }
+
+    /// Returns true if both votes are the same instance (`==` compares weights).
+    pub fn is_same(&self, other: &Vote<C>) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
     pub fn height(&self) -> Result<u64> {
         Ok(self.milestone()?.height)
     }
@@ -49,7 +70,12 @@ impl<C: VirtualVotingConfig> Vote<C> {
         let mut current = self.clone();
 
         while current.slot > since {
-            current = Vote::try_from(current.slot_boundary()?)?;
+            let slot_boundary = current.slot_boundary()?;
+            if slot_boundary.points_to(&current) {
+                break;
+            }
+
+            current = Vote::try_from(slot_boundary)?;
             weight += current.committee.online_weight();
         }
 
@@ -73,6 +99,25 @@ impl<C: VirtualVotingConfig> Vote<C> {
         Ok(range)
     }
 
+    pub fn common_milestone(&self, other: &Vote<C>) -> Result<Vote<C>> {
+        let (mut current, mut other) = (self.clone(), other.clone());
+        while !current.is_same(&other) {
+            match current.height()?.cmp(&other.height()?) {
+                Ordering::Greater => current = Vote::try_from(current.prev_milestone()?)?,
+                Ordering::Less => other = Vote::try_from(other.prev_milestone()?)?,
+                Ordering::Equal if current.prev_milestone()?.points_to(&current) => {
+                    return Err(NoCommonMilestone);
+                }
+                Ordering::Equal => {
+                    current = Vote::try_from(current.prev_milestone()?)?;
+                    other = Vote::try_from(other.prev_milestone()?)?;
+                }
+            }
+        }
+
+        Ok(current)
+    }
+
     pub fn weight(&self) -> (u64, u64, u64) {
         (
             self.cumulative_slot_weight,
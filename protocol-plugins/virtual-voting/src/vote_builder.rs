@@ -7,8 +7,8 @@ use common::{
 };
 
 use crate::{
-    ConsensusMechanism,
-    Error::{TimeMustIncrease, VotesMustNotBeEmpty},
+    Checkpoint, ConsensusMechanism,
+    Error::{EmptyCommittee, TimeMustIncrease, VotesMustNotBeEmpty},
     Issuer, Milestone, Result, VirtualVotingConfig, Vote, VoteRef, VoteRefs, VoteRefsByIssuer,
     Votes, VotesByIssuer,
 };
@@ -23,6 +23,7 @@ pub struct VoteBuilder<T: VirtualVotingConfig> {
     pub round: u64,
     pub referenced_round_weight: u64,
     pub committee: Committee,
+    pub epoch: u64,
     pub referenced_milestones: VoteRefsByIssuer<T>,
     pub milestone: Option<Milestone<T>>,
 }
@@ -44,8 +45,9 @@ impl<C: VirtualVotingConfig> VoteBuilder<C> {
             source,
             issuer: Issuer::User(issuer.clone()),
             time,
-            slot: heaviest_vote.slot,
+            slot: heaviest_vote.config.slot_oracle(time),
             committee: heaviest_vote.committee.clone(),
+            epoch: heaviest_vote.epoch,
             cumulative_slot_weight: heaviest_vote.cumulative_slot_weight,
             config: heaviest_vote.config.clone(),
             round: heaviest_vote.round,
@@ -69,7 +71,13 @@ impl<C: VirtualVotingConfig> VoteBuilder<C> {
             }
         }
 
-        // TODO: ROTATE COMMITTEE
+        // rotate the committee once a milestone that selected the next one got accepted
+        if let Some((epoch, committee)) = Self::scheduled_committee(heaviest_vote)?
+            && epoch > builder.epoch
+        {
+            builder.epoch = epoch;
+            builder.committee = committee;
+        }
 
         // build validator perception if issuer is part of the committee
         if let Some(validator) = builder.committee.member(issuer).cloned() {
@@ -82,7 +90,8 @@ impl<C: VirtualVotingConfig> VoteBuilder<C> {
 
     pub(crate) fn build_genesis(source: BlockMetadataRef, config: Arc<C>) -> Vote<C> {
         Vote::from(Arc::new_cyclic(|me| {
-            let committee = config.select_committee(None);
+            let committee =
+                Self::select_committee(&config, None).expect("genesis committee must be valid");
 
             Self {
                 source,
@@ -90,6 +99,7 @@ impl<C: VirtualVotingConfig> VoteBuilder<C> {
                 time: config.genesis_time(),
                 slot: 0,
                 committee: committee.clone(),
+                epoch: 0,
                 config,
                 cumulative_slot_weight: 0,
                 round: 0,
@@ -105,11 +115,66 @@ impl<C: VirtualVotingConfig> VoteBuilder<C> {
                     confirmed: me.into(),
                     prev: me.into(),
                     slot_boundary: me.into(),
+                    next_committee: None,
                 }),
             }
         }))
     }
 
+    pub(crate) fn build_checkpoint(
+        source: BlockMetadataRef,
+        config: Arc<C>,
+        checkpoint: &Checkpoint,
+    ) -> Vote<C> {
+        Vote::from(Arc::new_cyclic(|me| Self {
+            source,
+            issuer: Issuer::Genesis,
+            time: checkpoint.time,
+            slot: checkpoint.slot,
+            committee: checkpoint.committee.clone(),
+            epoch: checkpoint.epoch,
+            config,
+            cumulative_slot_weight: checkpoint.cumulative_slot_weight,
+            round: checkpoint.round,
+            referenced_round_weight: u64::MAX,
+            referenced_milestones: VoteRefsByIssuer::from_committee(
+                &checkpoint.committee,
+                &VoteRef::from(me),
+            ),
+            milestone: Some(Milestone {
+                height: checkpoint.height,
+                leader_weight: u64::MAX,
+                accepted: me.into(),
+                confirmed: me.into(),
+                prev: me.into(),
+                slot_boundary: me.into(),
+                next_committee: None,
+            }),
+        }))
+    }
+
+    pub(crate) fn build_anchor(source: BlockMetadataRef, checkpoint_vote: &Vote<C>) -> Vote<C> {
+        // the issuing time of the anchor is unknown, so it is treated like the genesis block to not
+        // violate the monotonicity of time for blocks that were issued before the checkpoint
+        Vote::from(Arc::new(Self {
+            source,
+            issuer: Issuer::Genesis,
+            time: checkpoint_vote.config.genesis_time(),
+            slot: 0,
+            committee: checkpoint_vote.committee.clone(),
+            epoch: checkpoint_vote.epoch,
+            config: checkpoint_vote.config.clone(),
+            cumulative_slot_weight: checkpoint_vote.cumulative_slot_weight,
+            round: checkpoint_vote.round,
+            referenced_round_weight: 0,
+            referenced_milestones: VoteRefsByIssuer::from_committee(
+                &checkpoint_vote.committee,
+                &checkpoint_vote.into(),
+            ),
+            milestone: None,
+        }))
+    }
+
     fn build_validator_perception(
         mut self,
         validator: &Member,
@@ -153,8 +218,20 @@ impl<C: VirtualVotingConfig> VoteBuilder<C> {
                     // otherwise inherit the heaviest tip's slot boundary
                     false => heaviest_tip.slot_boundary()?.clone(),
                 },
+                next_committee: None,
             });
 
+            // select the next committee when crossing an epoch boundary
+            let epoch = self.config.committee_epoch(&self);
+            if epoch > self.epoch
+                && let Some(milestone) = &mut self.milestone
+            {
+                milestone.next_committee = Some((
+                    epoch,
+                    Self::select_committee(&self.config, Some(&heaviest_tip))?,
+                ));
+            }
+
             // update cumulative slot weight
             let prev_accepted_slot = heaviest_tip.accepted_milestone()?.slot;
             self.cumulative_slot_weight += accepted.slot_weight_since(prev_accepted_slot)?;
@@ -203,8 +280,27 @@ impl<C: VirtualVotingConfig> VoteBuilder<C> {
         )
     }
 
+    fn select_committee(config: &C, vote: Option<&Vote<C>>) -> Result<Committee> {
+        let committee = config.select_committee(vote)?;
+        match committee.size() {
+            0 => Err(EmptyCommittee),
+            _ => Ok(committee),
+        }
+    }
+
+    fn scheduled_committee(vote: &Vote<C>) -> Result<Option<(u64, Committee)>> {
+        let Some(milestone) = &vote.milestone else {
+            return Ok(None);
+        };
+
+        Ok(Vote::try_from(&milestone.accepted)?
+            .milestone()?
+            .next_committee
+            .clone())
+    }
+
     fn offline_validators(&self, votes: &VotesByIssuer<C>) -> HashSet<IssuerID> {
-        let offline_threshold = self.slot - self.config.offline_threshold();
+        let offline_threshold = self.slot.saturating_sub(self.config.offline_threshold());
 
         // filter online validators that haven't voted since the offline threshold
         self.committee
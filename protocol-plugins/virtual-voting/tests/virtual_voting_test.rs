@@ -1,11 +1,13 @@
 use std::sync::Arc;
 
 use common::{
+    bft::{Committee, Member},
     blocks::BlockMetadataRef,
+    crypto::SigningKey,
     hash::{Hashable, Hasher},
     ids::BlockID,
 };
-use config::Config;
+use config::{CommitteeRotation, CommitteeSelection, Config, SlotDuration};
 use virtual_voting::{Vote, Votes};
 
 pub struct Block(u64);
@@ -187,3 +189,211 @@ fn test_consensus() -> virtual_voting::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_time_must_increase() -> virtual_voting::Result<()> {
+    let genesis = Vote::new_genesis(BlockMetadataRef::default(), Arc::new(Config::default()));
+    let members = genesis.committee.members();
+
+    let vote = Vote::new(
+        BlockMetadataRef::default(),
+        members[0].key(),
+        2,
+        Votes::from_iter(vec![genesis.clone()]),
+    )?;
+
+    let result = Vote::new(
+        BlockMetadataRef::default(),
+        members[1].key(),
+        1,
+        Votes::from_iter(vec![vote.clone()]),
+    );
+    assert!(matches!(
+        result,
+        Err(virtual_voting::Error::TimeMustIncrease)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_slot_transitions() -> virtual_voting::Result<()> {
+    let config = Config::default()
+        .with_genesis_time(100)
+        .with_slot_duration(SlotDuration::Static(10))
+        .with_offline_threshold(1);
+    let genesis = Vote::new_genesis(BlockMetadataRef::default(), Arc::new(config));
+    let members = genesis.committee.members();
+    let vote = |member: usize, time: u64, parents: &[&Vote<Config>]| {
+        Vote::new(
+            BlockMetadataRef::default(),
+            members[member].key(),
+            time,
+            Votes::from_iter(parents.iter().map(|v| (*v).clone())),
+        )
+    };
+
+    // slot 0: everybody votes for genesis
+    let vote1_0 = vote(0, 101, &[&genesis])?;
+    let vote2_0 = vote(1, 101, &[&genesis])?;
+    let vote3_0 = vote(2, 101, &[&genesis])?;
+    let vote4_0 = vote(3, 101, &[&genesis])?;
+    assert_eq!(vote1_0.slot, 0);
+
+    // slot 1: member 4 stops voting but is still within the offline threshold
+    let slot_0 = [&vote1_0, &vote2_0, &vote3_0, &vote4_0];
+    let vote1_1 = vote(0, 115, &slot_0)?;
+    let vote2_1 = vote(1, 115, &slot_0)?;
+    let vote3_1 = vote(2, 115, &slot_0)?;
+    assert_eq!(vote1_1.slot, 1);
+    assert!(vote1_1.committee.is_member_online(members[3].key()));
+
+    // slot 2: member 4 hasn't voted since slot 0 and is marked offline
+    let vote1_2 = vote(0, 125, &[&vote1_1, &vote2_1, &vote3_1, &vote4_0])?;
+    assert_eq!(vote1_2.slot, 2);
+    assert!(vote1_2.committee.is_member_online(members[0].key()));
+    assert!(vote1_2.committee.is_member_online(members[1].key()));
+    assert!(vote1_2.committee.is_member_online(members[2].key()));
+    assert!(!vote1_2.committee.is_member_online(members[3].key()));
+
+    Ok(())
+}
+
+#[test]
+fn test_common_milestone() -> virtual_voting::Result<()> {
+    let genesis = Vote::new_genesis(BlockMetadataRef::default(), Arc::new(Config::default()));
+    let members = genesis.committee.members();
+    let vote = |member: usize, time: u64, parents: &[&Vote<Config>]| {
+        Vote::new(
+            BlockMetadataRef::default(),
+            members[member].key(),
+            time,
+            Votes::from_iter(parents.iter().map(|v| (*v).clone())),
+        )
+    };
+
+    // competing milestones that all build on genesis
+    let vote1_1 = vote(0, 1, &[&genesis])?;
+    let vote2_1 = vote(1, 1, &[&genesis])?;
+    let vote3_1 = vote(2, 1, &[&genesis])?;
+    assert!(vote1_1.common_milestone(&vote2_1)?.is_same(&genesis));
+
+    // extend the chain of member 3
+    let vote1_2 = vote(0, 2, &[&vote1_1, &vote2_1, &vote3_1])?;
+    assert!(vote1_2.prev_milestone()?.points_to(&vote3_1));
+    assert!(vote1_2.common_milestone(&vote3_1)?.is_same(&vote3_1));
+    assert!(vote3_1.common_milestone(&vote1_2)?.is_same(&vote3_1));
+    assert!(vote1_2.common_milestone(&vote1_1)?.is_same(&genesis));
+    assert!(vote1_2.common_milestone(&vote1_2)?.is_same(&vote1_2));
+
+    Ok(())
+}
+
+#[test]
+fn test_committee_rotation() -> virtual_voting::Result<()> {
+    fn select_committee(_: &Config, vote: Option<&Vote<Config>>) -> Committee {
+        // replace the 4th validator with a new one after the first epoch
+        let validators: &[u8] = match vote {
+            None => &[1, 2, 3, 4],
+            Some(_) => &[1, 2, 3, 5],
+        };
+
+        Committee::from(
+            validators
+                .iter()
+                .map(|seed| Member::new(SigningKey::from([*seed; 32]).issuer_id())),
+        )
+    }
+
+    let config = Config::default()
+        .with_committee_selection(CommitteeSelection::Custom(select_committee))
+        .with_committee_rotation(CommitteeRotation::EveryHeights(2));
+    let genesis = Vote::new_genesis(BlockMetadataRef::default(), Arc::new(config));
+    let old_validator = SigningKey::from([4; 32]).issuer_id();
+    let new_validator = SigningKey::from([5; 32]).issuer_id();
+    assert!(genesis.committee.member(&old_validator).is_some());
+
+    // let all validators vote on all votes of the previous round (votes only reference their
+    // parents weakly, so we keep all of them alive)
+    let mut votes = vec![genesis.clone()];
+    let mut all_votes = Vec::new();
+    let mut scheduled_committee = false;
+    for round in 1..=6 {
+        votes = [1, 2, 3, 4, 5]
+            .iter()
+            .map(|seed| {
+                Vote::new(
+                    BlockMetadataRef::default(),
+                    &SigningKey::from([*seed; 32]).issuer_id(),
+                    round,
+                    Votes::from_iter(votes.clone()),
+                )
+            })
+            .collect::<virtual_voting::Result<_>>()?;
+
+        scheduled_committee |= votes
+            .iter()
+            .any(|v| v.milestone().is_ok_and(|m| m.next_committee.is_some()));
+        all_votes.extend(votes.clone());
+    }
+
+    // the next committee was committed into a milestone and took effect after its acceptance
+    assert!(scheduled_committee);
+    for vote in &votes {
+        assert_eq!(vote.epoch, 1);
+        assert!(vote.committee.member(&old_validator).is_none());
+        assert!(vote.committee.member(&new_validator).is_some());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_committee_rotation_without_epoch_length() -> virtual_voting::Result<()> {
+    for rotation in [
+        CommitteeRotation::EverySlots(0),
+        CommitteeRotation::EveryHeights(0),
+    ] {
+        let config = Config::default().with_committee_rotation(rotation);
+        let genesis = Vote::new_genesis(BlockMetadataRef::default(), Arc::new(config));
+        let members = genesis.committee.members();
+
+        // an epoch length of 0 never rotates the committee
+        let vote = Vote::new(
+            BlockMetadataRef::default(),
+            members[0].key(),
+            1,
+            Votes::from_iter(vec![genesis.clone()]),
+        )?;
+        assert_eq!(vote.epoch, 0);
+        assert!(vote.milestone()?.next_committee.is_none());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_committee_is_rejected() -> virtual_voting::Result<()> {
+    fn select_committee(_: &Config, vote: Option<&Vote<Config>>) -> Committee {
+        match vote {
+            None => Committee::from([Member::new(SigningKey::from([1; 32]).issuer_id())]),
+            Some(_) => Committee::from([]),
+        }
+    }
+
+    let config = Config::default()
+        .with_committee_selection(CommitteeSelection::Custom(select_committee))
+        .with_committee_rotation(CommitteeRotation::EveryHeights(1));
+    let genesis = Vote::new_genesis(BlockMetadataRef::default(), Arc::new(config));
+
+    // the validator would have to hand over to an empty committee at the epoch boundary
+    let result = Vote::new(
+        BlockMetadataRef::default(),
+        &SigningKey::from([1; 32]).issuer_id(),
+        1,
+        Votes::from_iter(vec![genesis.clone()]),
+    );
+    assert!(matches!(result, Err(virtual_voting::Error::EmptyCommittee)));
+
+    Ok(())
+}
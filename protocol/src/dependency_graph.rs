@@ -0,0 +1,118 @@
+use std::{collections::HashMap, fmt::Write, future::Future};
+
+use common::traced::panic_reason;
+use tokio::task::JoinSet;
+
+/// The dependencies between the plugins of a [`Plugins`](crate::Plugins) registry.
+///
+/// Plugins are identified by the order they were registered in, and a plugin depends on every
+/// plugin that it loaded (or that was provided) while it was constructed.
+#[derive(Default)]
+pub struct DependencyGraph {
+    names: Vec<&'static str>,
+    dependencies: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, plugin: usize) -> &'static str {
+        self.names[plugin]
+    }
+
+    /// Returns the plugins that the given plugin depends on.
+    pub fn dependencies(&self, plugin: usize) -> &[usize] {
+        &self.dependencies[plugin]
+    }
+
+    /// Exports the graph in the DOT format, with edges pointing from plugins to their
+    /// dependencies.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph plugins {\n");
+        for (plugin, name) in self.names.iter().enumerate() {
+            let _ = writeln!(dot, "    p{plugin} [label=\"{name}\"];");
+        }
+        for (plugin, dependencies) in self.dependencies.iter().enumerate() {
+            for dependency in dependencies {
+                let _ = writeln!(dot, "    p{plugin} -> p{dependency};");
+            }
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    pub(crate) fn add(&mut self, name: &'static str, mut dependencies: Vec<usize>) -> usize {
+        dependencies.sort_unstable();
+        dependencies.dedup();
+        self.names.push(name);
+        self.dependencies.push(dependencies);
+
+        self.names.len() - 1
+    }
+
+    /// Runs the action for all plugins concurrently, but only after it completed for the
+    /// dependencies of a plugin (or for its dependents if `reverse` is set).
+    ///
+    /// The outcome of every action (the panic message if it panicked) is passed to `completed`,
+    /// which returns whether the plugins that wait for it can run.
+    pub(crate) async fn run<F, Fut, C>(&self, reverse: bool, action: F, mut completed: C)
+    where
+        F: Fn(usize) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+        C: FnMut(usize, Result<(), String>) -> bool,
+    {
+        // the number of plugins that every plugin waits for, and the plugins that wait for it
+        let mut pending = vec![0; self.len()];
+        let mut waiting = vec![Vec::new(); self.len()];
+        for (plugin, dependencies) in self.dependencies.iter().enumerate() {
+            for &dependency in dependencies {
+                let (first, then) = match reverse {
+                    false => (dependency, plugin),
+                    true => (plugin, dependency),
+                };
+                pending[then] += 1;
+                waiting[first].push(then);
+            }
+        }
+
+        let mut running = JoinSet::new();
+        // the plugins of the running tasks
+        let mut tasks = HashMap::new();
+        let spawn = |running: &mut JoinSet<()>, tasks: &mut HashMap<_, _>, plugin: usize| {
+            tasks.insert(running.spawn(action(plugin)).id(), plugin);
+        };
+        for plugin in (0..self.len()).filter(|plugin| pending[*plugin] == 0) {
+            spawn(&mut running, &mut tasks, plugin);
+        }
+
+        while let Some(result) = running.join_next_with_id().await {
+            let (plugin, outcome) = match result {
+                Ok((id, ())) => (tasks[&id], Ok(())),
+                Err(error) => (
+                    tasks[&error.id()],
+                    Err(match error.try_into_panic() {
+                        Ok(payload) => panic_reason(payload),
+                        Err(error) => error.to_string(),
+                    }),
+                ),
+            };
+            if !completed(plugin, outcome) {
+                continue;
+            }
+
+            for &waiting in &waiting[plugin] {
+                pending[waiting] -= 1;
+                if pending[waiting] == 0 {
+                    spawn(&mut running, &mut tasks, waiting);
+                }
+            }
+        }
+    }
+}
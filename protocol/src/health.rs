@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// The health that a plugin reports in [`Plugin::health`](crate::Plugin::health).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Health {
+    Healthy,
+    /// The plugin works, but not as intended (e.g. because it is not connected).
+    Degraded(String),
+    /// The plugin stopped working.
+    Failed(String),
+}
+
+impl Health {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Health::Healthy)
+    }
+
+    /// Returns the worse of both healths (the first one if they are equally bad).
+    pub fn worst(self, other: Health) -> Health {
+        match (&self, &other) {
+            (Health::Failed(_), _) => self,
+            (_, Health::Failed(_)) => other,
+            (Health::Degraded(_), _) => self,
+            (_, Health::Degraded(_)) => other,
+            _ => self,
+        }
+    }
+}
+
+/// A plugin that failed, which is reported by [`Plugins::failed`](crate::Plugins::failed).
+#[derive(Clone, Debug)]
+pub struct PluginFailure {
+    pub plugin: &'static str,
+    pub reason: String,
+}
+
+impl fmt::Display for PluginFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "plugin `{}` failed: {}", self.plugin, self.reason)
+    }
+}
@@ -1,7 +1,13 @@
 mod config;
+mod dependency_graph;
+mod health;
+mod lifecycle;
 mod managed_plugin;
 mod plugin;
 mod plugins;
 mod protocol;
 
-pub use crate::{config::*, managed_plugin::*, plugin::*, plugins::*, protocol::*};
+pub use crate::{
+    config::*, dependency_graph::*, health::*, lifecycle::PluginState, managed_plugin::*,
+    plugin::*, plugins::*, protocol::*,
+};
@@ -0,0 +1,58 @@
+use std::sync::Mutex;
+
+use common::rx::Event;
+
+use crate::PluginFailure;
+
+/// The lifecycle states of a plugin.
+///
+/// Plugins are `Loaded` until the protocol starts them, and `Stopped` once they were shut down.
+/// Plugins that failed (because their start, their shutdown or one of their workers panicked) stay
+/// `Failed`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PluginState {
+    Loaded,
+    Starting,
+    Running,
+    ShuttingDown,
+    Stopped,
+    Failed(String),
+}
+
+/// Tracks the state of a plugin and reports its failures.
+pub(crate) struct Lifecycle {
+    name: &'static str,
+    state: Mutex<PluginState>,
+    failures: Event<PluginFailure>,
+}
+
+impl Lifecycle {
+    pub(crate) fn new(name: &'static str, failures: Event<PluginFailure>) -> Self {
+        Self {
+            name,
+            state: Mutex::new(PluginState::Loaded),
+            failures,
+        }
+    }
+
+    pub(crate) fn state(&self) -> PluginState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Moves the plugin to the given state, unless it failed already.
+    pub(crate) fn transition(&self, state: PluginState) {
+        let mut current = self.state.lock().unwrap();
+        if !matches!(*current, PluginState::Failed(_)) {
+            *current = state;
+        }
+    }
+
+    pub(crate) fn fail(&self, reason: String) {
+        let failure = PluginFailure {
+            plugin: self.name,
+            reason: reason.clone(),
+        };
+        self.transition(PluginState::Failed(reason));
+        self.failures.trigger(&failure);
+    }
+}
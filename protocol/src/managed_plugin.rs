@@ -3,7 +3,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use tracing::Span;
 
-use crate::{Plugin, Plugins};
+use crate::{Health, Plugin, Plugins};
 
 #[async_trait]
 pub trait ManagedPlugin: Sized + Send + Sync {
@@ -17,6 +17,10 @@ pub trait ManagedPlugin: Sized + Send + Sync {
         // do nothing by default
     }
 
+    fn health(&self) -> Health {
+        Health::Healthy
+    }
+
     fn span(&self) -> Span;
 }
 
@@ -30,6 +34,10 @@ impl<T: ManagedPlugin> Plugin for T {
         ManagedPlugin::shutdown(self).await;
     }
 
+    fn health(&self) -> Health {
+        ManagedPlugin::health(self)
+    }
+
     fn span(&self) -> Span {
         ManagedPlugin::span(self)
     }
@@ -1,21 +1,33 @@
 use async_trait::async_trait;
 use tracing::Span;
 
+use crate::Health;
+
 #[async_trait]
 pub trait Plugin: Send + Sync {
     fn plugin_name(&self) -> &'static str {
-        std::any::type_name::<Self>()
-            .split('<')
-            .next()
-            .unwrap()
-            .rsplit("::")
-            .next()
-            .unwrap()
+        short_type_name(std::any::type_name::<Self>())
     }
 
     async fn start(&self) {}
 
     async fn shutdown(&self) {}
 
+    /// Reports whether the plugin works as intended while it is running.
+    fn health(&self) -> Health {
+        Health::Healthy
+    }
+
     fn span(&self) -> Span;
 }
+
+/// Strips the module path and the generic parameters from a type name.
+pub(crate) fn short_type_name(type_name: &'static str) -> &'static str {
+    type_name
+        .split('<')
+        .next()
+        .unwrap()
+        .rsplit("::")
+        .next()
+        .unwrap()
+}
@@ -1,51 +1,192 @@
-use std::{any::Any, sync::Arc};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Arc,
+};
 
-use common::collections::AnyMap;
-use tracing::{Instrument, debug};
+use common::{collections::AnyMap, rx::Event, traced::WorkerFailure, with};
+use tracing::{Instrument, debug, error};
 
-use crate::{ManagedPlugin, Plugin};
+use crate::{
+    DependencyGraph, Health, ManagedPlugin, Plugin, PluginFailure, PluginState,
+    lifecycle::Lifecycle, plugin::short_type_name,
+};
 
 #[derive(Default)]
 pub struct Plugins {
+    /// Triggered when a plugin fails (see [`PluginState::Failed`]).
+    pub failed: Event<PluginFailure>,
     instances: AnyMap,
     trait_objects: Vec<Arc<dyn Plugin>>,
+    lifecycles: Vec<Arc<Lifecycle>>,
+    graph: DependencyGraph,
+    indices: HashMap<TypeId, usize>,
+    /// The plugins that are being constructed, with the dependencies they loaded so far.
+    loading: Vec<Loading>,
 }
 
 impl Plugins {
+    /// Starts all plugins, where independent plugins start concurrently and every plugin starts
+    /// after its dependencies. Plugins whose dependencies failed to start are not started.
     pub async fn start(&self) {
-        for plugin in self.iter() {
-            plugin.start().instrument(plugin.span()).await;
-        }
+        self.graph
+            .run(
+                false,
+                |plugin| {
+                    self.lifecycles[plugin].transition(PluginState::Starting);
+                    let plugin = self.trait_objects[plugin].clone();
+                    async move { plugin.start().instrument(plugin.span()).await }
+                },
+                |plugin, outcome| match outcome {
+                    Ok(()) => {
+                        self.lifecycles[plugin].transition(PluginState::Running);
+                        true
+                    }
+                    Err(reason) => {
+                        error!(
+                            "plugin {} failed to start: {reason}",
+                            self.graph.name(plugin)
+                        );
+                        self.lifecycles[plugin].fail(reason);
+                        false
+                    }
+                },
+            )
+            .await;
     }
 
+    /// Shuts down all plugins, where every plugin shuts down before its dependencies.
     pub async fn shutdown(&self) {
-        for plugin in self.iter().rev() {
-            plugin.shutdown().instrument(plugin.span()).await;
-        }
+        self.graph
+            .run(
+                true,
+                |plugin| {
+                    self.lifecycles[plugin].transition(PluginState::ShuttingDown);
+                    let plugin = self.trait_objects[plugin].clone();
+                    async move { plugin.shutdown().instrument(plugin.span()).await }
+                },
+                |plugin, outcome| {
+                    match outcome {
+                        Ok(()) => self.lifecycles[plugin].transition(PluginState::Stopped),
+                        Err(reason) => {
+                            error!(
+                                "plugin {} failed to shut down: {reason}",
+                                self.graph.name(plugin)
+                            );
+                            self.lifecycles[plugin].fail(reason)
+                        }
+                    }
+                    true
+                },
+            )
+            .await;
+    }
+
+    /// Returns the state of the plugin (in the order of [`Self::iter`]).
+    pub fn state(&self, plugin: usize) -> PluginState {
+        self.lifecycles[plugin].state()
+    }
+
+    /// Returns the health of the protocol, which is the worst health of its plugins. Failed plugins
+    /// are unhealthy regardless of the health that they report.
+    pub fn health(&self) -> Health {
+        self.trait_objects
+            .iter()
+            .zip(&self.lifecycles)
+            .map(|(plugin, lifecycle)| {
+                let health = match lifecycle.state() {
+                    PluginState::Failed(reason) => Health::Failed(reason),
+                    _ => plugin.health(),
+                };
+                match health {
+                    Health::Healthy => Health::Healthy,
+                    Health::Degraded(reason) => {
+                        Health::Degraded(format!("{}: {reason}", plugin.plugin_name()))
+                    }
+                    Health::Failed(reason) => {
+                        Health::Failed(format!("{}: {reason}", plugin.plugin_name()))
+                    }
+                }
+            })
+            .fold(Health::Healthy, Health::worst)
+    }
+
+    /// Returns an event for the workers of the plugin that is being constructed (see
+    /// [`traced::worker`](common::traced::worker)), whose failures fail the plugin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no plugin is being constructed.
+    pub fn worker_failures(&self) -> Event<WorkerFailure> {
+        let loading = self
+            .loading
+            .last()
+            .expect("worker failures are only available while a plugin is constructed");
+        let lifecycle = loading.lifecycle.clone();
+
+        let failures = Event::new();
+        failures
+            .subscribe(with!(lifecycle: move |failure: &WorkerFailure| {
+                lifecycle.fail(failure.to_string())
+            }))
+            .retain();
+
+        failures
     }
 
     pub fn provide<U: Any + Send + Sync + Plugin + 'static>(&mut self, instance: Arc<U>) -> Arc<U> {
         if let Some(existing) = self.instances.get::<Arc<U>>() {
-            return existing.clone();
+            let existing = existing.clone();
+            self.depend_on::<U>();
+            return existing;
         }
         instance.span().in_scope(|| debug!("plugin provided"));
 
-        self.instances.insert(instance.clone());
-        self.trait_objects.push(instance.clone());
+        let lifecycle = Lifecycle::new(instance.plugin_name(), self.failed.clone());
+        self.register(instance.clone(), Arc::new(lifecycle), Vec::new());
+        self.depend_on::<U>();
 
         instance
     }
 
+    /// Loads the plugin (and the plugins that it loads in turn), unless it was loaded already.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the plugin (indirectly) loads itself while it is constructed.
     pub fn load<U: Any + Send + Sync + ManagedPlugin + 'static>(&mut self) -> Arc<U> {
         if let Some(existing) = self.instances.get::<Arc<U>>() {
-            return existing.clone();
+            let existing = existing.clone();
+            self.depend_on::<U>();
+            return existing;
+        }
+
+        let name = short_type_name(std::any::type_name::<U>());
+        if let Some(start) = self
+            .loading
+            .iter()
+            .position(|loading| loading.type_id == TypeId::of::<U>())
+        {
+            let cycle: Vec<_> = self.loading[start..]
+                .iter()
+                .map(|loading| loading.name)
+                .chain([name])
+                .collect();
+            panic!("plugin dependency cycle: {}", cycle.join(" -> "));
         }
 
+        self.loading.push(Loading {
+            type_id: TypeId::of::<U>(),
+            name,
+            lifecycle: Arc::new(Lifecycle::new(name, self.failed.clone())),
+            dependencies: Vec::new(),
+        });
         let instance = U::new(self);
+        let loading = self.loading.pop().expect("pushed before constructing");
         instance.span().in_scope(|| debug!("plugin loaded"));
 
-        self.instances.insert(instance.clone());
-        self.trait_objects.push(instance.clone());
+        self.register(instance.clone(), loading.lifecycle, loading.dependencies);
+        self.depend_on::<U>();
 
         instance
     }
@@ -57,4 +198,42 @@ impl Plugins {
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Arc<dyn Plugin>> {
         self.trait_objects.iter()
     }
+
+    /// Returns the dependencies between the plugins, in the order that [`Self::iter`] returns the
+    /// plugins in.
+    pub fn graph(&self) -> &DependencyGraph {
+        &self.graph
+    }
+
+    fn register<U: Any + Send + Sync + Plugin + 'static>(
+        &mut self,
+        instance: Arc<U>,
+        lifecycle: Arc<Lifecycle>,
+        dependencies: Vec<usize>,
+    ) {
+        let index = self.graph.add(instance.plugin_name(), dependencies);
+        self.indices.insert(TypeId::of::<U>(), index);
+        self.instances.insert(instance.clone());
+        self.trait_objects.push(instance);
+        self.lifecycles.push(lifecycle);
+    }
+
+    /// Records that the plugin that is being constructed (if any) depends on `U`.
+    fn depend_on<U: Any>(&mut self) {
+        if let (Some(loading), Some(index)) = (
+            self.loading.last_mut(),
+            self.indices.get(&TypeId::of::<U>()),
+        ) {
+            loading.dependencies.push(*index);
+        }
+    }
+}
+
+/// A plugin that is being constructed.
+struct Loading {
+    type_id: TypeId,
+    name: &'static str,
+    lifecycle: Arc<Lifecycle>,
+    /// The plugins that the plugin loaded so far.
+    dependencies: Vec<usize>,
 }
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use tracing::info;
 
-use crate::{Plugins, ProtocolConfig};
+use crate::{Health, Plugins, ProtocolConfig};
 
 pub struct Protocol {
     pub plugins: Plugins,
@@ -27,4 +27,9 @@ impl Protocol {
         self.plugins.shutdown().await;
         info!("protocol stopped");
     }
+
+    /// Returns the health of the protocol (see [`Plugins::health`]).
+    pub fn health(&self) -> Health {
+        self.plugins.health()
+    }
 }
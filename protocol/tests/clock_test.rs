@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use block_factory::BlockFactory;
+use clock::MockClock;
+use common::{blocks::Block, crypto::SigningKey};
+use config::{Config, ProtocolParams};
+use protocol::Protocol;
+
+#[test]
+fn test_injected_clock() {
+    let clock = Arc::new(MockClock::new(1_000));
+    let protocol = Protocol::new(
+        Config::default().with_protocol_params(ProtocolParams::default().with_clock(clock.clone())),
+    );
+    let block_factory = protocol.plugins.get::<BlockFactory<Config>>().unwrap();
+
+    clock.advance(500);
+    let Block::NetworkBlock(_, network_block) =
+        block_factory.create_block(&SigningKey::from([1; 32]))
+    else {
+        panic!("expected a network block");
+    };
+    assert_eq!(network_block.issuing_time, 1_500);
+}
@@ -0,0 +1,99 @@
+use std::{sync::Arc, time::Duration};
+
+use clock::{Clock, SystemClock};
+use common::crypto::SigningKey;
+use config::{Config, ConfigError, ConfigLoader};
+use consensus::Consensus;
+use networking::Networking;
+use pruning::PruningConfig;
+use sim::{Network, Node};
+use solidifier::SolidifierConfig;
+use tracing::info_span;
+use validator::ValidatorConfig;
+
+#[tokio::test]
+async fn test_validator_from_config_file() {
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let genesis_time = clock.now();
+    let committee: String = (1..=4u8)
+        .map(|i| {
+            let issuer_id = SigningKey::from([i; 32]).issuer_id();
+            format!("[[committee]]\nid = \"{issuer_id:?}\"\n")
+        })
+        .collect();
+    let content = format!(
+        r#"
+            genesis_time = {genesis_time}
+            plugins = "validator"
+
+            [validator]
+            seed = "{}"
+
+            [solidifier]
+            max_attempts = 2
+
+            [pruning]
+            depth = 8
+
+            {committee}
+            "#,
+        "04".repeat(32),
+    );
+    let config = ConfigLoader::default().load_toml(&content).unwrap();
+
+    assert_eq!(config.validator_id(), SigningKey::from([4; 32]).issuer_id());
+    assert_eq!(config.solidifier_max_attempts(), 2);
+    assert_eq!(config.pruning_depth(), 8);
+
+    // the node of the config file completes the committee of the other validators
+    let network = Network::default();
+    let mut nodes = vec![Node::new(info_span!("node", id = 4), || {
+        ConfigLoader::default().load_toml(&content).unwrap()
+    })];
+    for i in 1..=3u8 {
+        nodes.push(Node::new_validator(
+            info_span!("node", id = i),
+            SigningKey::from([i; 32]),
+            clock.clone(),
+            genesis_time,
+        ));
+    }
+    for node in &nodes {
+        node.plugins
+            .get::<Networking<Config>>()
+            .unwrap()
+            .connect(&network)
+            .await;
+        node.start().await;
+    }
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let consensus = nodes[0].plugins.get::<Consensus<Config>>().unwrap();
+    let height = consensus
+        .latest_accepted_milestone
+        .get()
+        .as_ref()
+        .and_then(|vote| vote.height().ok())
+        .unwrap_or(0);
+    assert!(height > 0, "no milestone was accepted");
+
+    for node in &nodes {
+        node.shutdown().await;
+    }
+}
+
+#[test]
+fn test_invalid_validator_seed() {
+    let error = ConfigLoader::default()
+        .load_toml("[validator]\nseed = \"0xabc\"")
+        .err()
+        .unwrap();
+
+    assert!(matches!(
+        &error,
+        ConfigError::InvalidValue { key, .. } if key == "validator.seed"
+    ));
+    // the seed is secret, so it is not part of the message
+    assert!(!error.to_string().contains("abc"), "{error}");
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use common::{
+    bft::Equivocation,
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::Id,
+    networking::{Message, Network as _, Recipients},
+};
+use config::Config;
+use consensus::Consensus;
+use networking::Networking;
+use protocol::Protocol;
+use sim::Network;
+
+fn milestone(member: u8, issuing_time: u64) -> Block {
+    NetworkBlock::new(
+        vec![Id::default()],
+        issuing_time,
+        vec![],
+        &SigningKey::from([member; 32]),
+    )
+    .into()
+}
+
+#[tokio::test]
+async fn test_received_evidence_is_verified_and_gossiped() {
+    let node = Protocol::new(Config::default());
+    let network = Network::default();
+    node.plugins
+        .get::<Networking<Config>>()
+        .unwrap()
+        .connect(&network)
+        .await;
+    node.start().await;
+
+    let consensus = node.plugins.get::<Consensus<Config>>().unwrap();
+    let (detected, mut detections) = tokio::sync::mpsc::unbounded_channel();
+    let _subscription = consensus.equivocations.subscribe(move |equivocation| {
+        let _ = detected.send(equivocation.clone());
+    });
+
+    // a peer that learned about the equivocation sends the evidence to the node
+    let mut peer = network.endpoint().await;
+    let evidence = Equivocation::new(milestone(1, 1), milestone(1, 2)).unwrap();
+    peer.outbound
+        .send((Recipients::All, Message::Equivocation(evidence.clone())))
+        .unwrap();
+
+    // the node processes the blocks of the evidence and reaches the same conclusion
+    let equivocation = tokio::time::timeout(Duration::from_secs(5), detections.recv())
+        .await
+        .expect("equivocation must be detected")
+        .unwrap();
+    assert_eq!(equivocation.issuer_id(), evidence.issuer_id());
+
+    // and passes the evidence on to its peers
+    let gossiped = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some((_, Message::Equivocation(equivocation))) = peer.inbound.recv().await {
+                return equivocation;
+            }
+        }
+    })
+    .await
+    .expect("evidence must be gossiped");
+    assert_eq!(
+        gossiped.blocks().each_ref().map(|block| block.id().clone()),
+        evidence.blocks().each_ref().map(|block| block.id().clone())
+    );
+
+    node.shutdown().await;
+}
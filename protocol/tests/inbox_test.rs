@@ -0,0 +1,112 @@
+use std::{collections::HashSet, sync::Arc};
+
+use clock::MockClock;
+use common::{
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::{Id, IssuerID},
+    networking::PeerID,
+};
+use config::{Config, ProtocolParams};
+use inbox::{BlockValidator, Error, Inbox, InboxConfigParams, ValidationError};
+use protocol::{Protocol, ProtocolConfig};
+
+#[test]
+fn test_time_too_far_in_future() {
+    let clock = Arc::new(MockClock::new(1_000));
+    let protocol = Protocol::new(
+        Config::default()
+            .with_max_time_drift(500)
+            .with_protocol_params(ProtocolParams::default().with_clock(clock.clone())),
+    );
+    let inbox = protocol.plugins.get::<Inbox<Config>>().unwrap();
+    let peer = PeerID(2);
+
+    let block = |issuing_time| -> Block {
+        NetworkBlock::new(
+            vec![Id::default()],
+            issuing_time,
+            vec![],
+            &SigningKey::from([1; 32]),
+        )
+        .into()
+    };
+
+    assert_eq!(
+        inbox.send_from(peer, block(1_501)),
+        Err(Error::TimeTooFarInFuture {
+            time: 1_501,
+            now: 1_000,
+            max_drift: 500
+        })
+    );
+    assert_eq!(inbox.dropped(), 1);
+    assert_eq!(inbox.depth(), 0);
+
+    assert_eq!(inbox.send_from(peer, block(1_500)), Ok(()));
+    assert_eq!(inbox.depth(), 1);
+
+    // the dropped block is admitted once the clock caught up with it
+    clock.advance(1);
+    assert_eq!(inbox.send_from(peer, block(1_501)), Ok(()));
+    assert_eq!(inbox.depth(), 2);
+}
+
+struct AllowedIssuers(HashSet<IssuerID>);
+
+impl BlockValidator for AllowedIssuers {
+    fn validate(&self, block: &Block) -> Result<(), ValidationError> {
+        match block {
+            Block::NetworkBlock(_, network_block) if !self.0.contains(&network_block.issuer_id) => {
+                Err(ValidationError::Rule {
+                    rule: "unknown_issuer",
+                    reason: format!("issuer {} is not allowed", network_block.issuer_id),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn test_invalid_blocks_are_not_queued() {
+    let protocol = Protocol::new(Config::default().with_params(InboxConfigParams {
+        max_parents: 2,
+        ..Default::default()
+    }));
+    let inbox = protocol.plugins.get::<Inbox<Config>>().unwrap();
+    let peer = PeerID(2);
+
+    let block = |parents, signer| -> Block {
+        NetworkBlock::new(parents, 0, vec![], &SigningKey::from([signer; 32])).into()
+    };
+
+    assert_eq!(
+        inbox.send_from(peer, block(vec![], 1)),
+        Err(Error::Invalid(ValidationError::NoParents))
+    );
+    assert_eq!(
+        inbox.send_from(peer, block(vec![Id::from([1; 32]); 3], 1)),
+        Err(Error::Invalid(ValidationError::TooManyParents {
+            count: 3,
+            max: 2
+        }))
+    );
+    assert_eq!(inbox.dropped(), 2);
+    assert_eq!(inbox.depth(), 0);
+
+    // plugins add their own rules
+    inbox.register_validator(Arc::new(AllowedIssuers(HashSet::from([SigningKey::from(
+        [1; 32],
+    )
+    .issuer_id()]))));
+    assert!(matches!(
+        inbox.send_from(peer, block(vec![Id::default()], 2)),
+        Err(Error::Invalid(ValidationError::Rule {
+            rule: "unknown_issuer",
+            ..
+        }))
+    ));
+    assert_eq!(inbox.send_from(peer, block(vec![Id::default()], 1)), Ok(()));
+    assert_eq!(inbox.depth(), 1);
+}
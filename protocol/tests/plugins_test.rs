@@ -0,0 +1,340 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use common::{rx::Event, traced, traced::WorkerFailure};
+use protocol::{Health, ManagedPlugin, Plugin, PluginFailure, PluginState, Plugins};
+use tokio::sync::Barrier;
+use tracing::{Span, info_span};
+
+/// Records the events of the test plugins.
+struct Log {
+    events: Mutex<Vec<String>>,
+    barrier: Barrier,
+}
+
+impl Log {
+    fn record(&self, event: String) {
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn events(&self) -> Vec<String> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+impl Plugin for Log {
+    fn span(&self) -> Span {
+        info_span!("log")
+    }
+}
+
+fn plugins() -> Plugins {
+    let mut plugins = Plugins::default();
+    plugins.provide(Arc::new(Log {
+        events: Mutex::new(Vec::new()),
+        barrier: Barrier::new(2),
+    }));
+
+    plugins
+}
+
+/// A plugin that takes a while to start, so that its dependents would notice if they started
+/// before it completed.
+struct Storage {
+    log: Arc<Log>,
+}
+
+#[async_trait]
+impl ManagedPlugin for Storage {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        Arc::new(Self {
+            log: plugins.get::<Log>().unwrap(),
+        })
+    }
+
+    async fn start(&self) {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.log.record("start Storage".to_string());
+    }
+
+    async fn shutdown(&self) {
+        self.log.record("shutdown Storage".to_string());
+    }
+
+    fn span(&self) -> Span {
+        info_span!("storage")
+    }
+}
+
+/// A plugin that depends on [`Storage`] and waits for its sibling to start at the same time.
+struct Service<const N: usize> {
+    log: Arc<Log>,
+}
+
+#[async_trait]
+impl<const N: usize> ManagedPlugin for Service<N> {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        plugins.load::<Storage>();
+        Arc::new(Self {
+            log: plugins.get::<Log>().unwrap(),
+        })
+    }
+
+    async fn start(&self) {
+        self.log.record(format!("start Service{N}"));
+        self.log.barrier.wait().await;
+    }
+
+    async fn shutdown(&self) {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.log.record(format!("shutdown Service{N}"));
+    }
+
+    fn span(&self) -> Span {
+        info_span!("service", n = N)
+    }
+}
+
+#[tokio::test]
+async fn test_start_and_shutdown_respect_dependencies() {
+    let mut plugins = plugins();
+    plugins.load::<Service<1>>();
+    plugins.load::<Service<2>>();
+    let log = plugins.get::<Log>().unwrap();
+
+    // the services wait for each other, so they only start if they start concurrently
+    tokio::time::timeout(Duration::from_secs(1), plugins.start())
+        .await
+        .expect("independent plugins did not start concurrently");
+    let events = log.events();
+    assert_eq!(events[0], "start Storage");
+    assert_eq!(events.len(), 3);
+    assert!((0..4).all(|plugin| plugins.state(plugin) == PluginState::Running));
+
+    plugins.shutdown().await;
+    assert_eq!(log.events()[5], "shutdown Storage");
+    assert!((0..4).all(|plugin| plugins.state(plugin) == PluginState::Stopped));
+}
+
+#[test]
+fn test_graph() {
+    let mut plugins = plugins();
+    plugins.load::<Service<1>>();
+
+    let graph = plugins.graph();
+    let names: Vec<_> = (0..graph.len()).map(|plugin| graph.name(plugin)).collect();
+    assert_eq!(names, ["Log", "Storage", "Service"]);
+
+    // dependencies are recorded for loaded and provided plugins, but not for plugins that are
+    // only looked up
+    assert!(graph.dependencies(1).is_empty());
+    assert_eq!(graph.dependencies(2), [1]);
+
+    assert_eq!(
+        graph.to_dot(),
+        "digraph plugins {\n    \
+             p0 [label=\"Log\"];\n    \
+             p1 [label=\"Storage\"];\n    \
+             p2 [label=\"Service\"];\n    \
+             p2 -> p1;\n\
+         }\n"
+    );
+}
+
+struct Ping;
+
+impl ManagedPlugin for Ping {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        plugins.load::<Pong>();
+        Arc::new(Self)
+    }
+
+    fn span(&self) -> Span {
+        info_span!("ping")
+    }
+}
+
+struct Pong;
+
+impl ManagedPlugin for Pong {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        plugins.load::<Ping>();
+        Arc::new(Self)
+    }
+
+    fn span(&self) -> Span {
+        info_span!("pong")
+    }
+}
+
+#[test]
+#[should_panic(expected = "plugin dependency cycle: Ping -> Pong -> Ping")]
+fn test_cycles_are_detected() {
+    plugins().load::<Ping>();
+}
+
+/// Collects the failures that the registry reports.
+fn failures(plugins: &Plugins) -> Arc<Mutex<Vec<String>>> {
+    let failures = Arc::new(Mutex::new(Vec::new()));
+    plugins
+        .failed
+        .subscribe({
+            let failures = failures.clone();
+            move |failure: &PluginFailure| failures.lock().unwrap().push(failure.to_string())
+        })
+        .retain();
+
+    failures
+}
+
+/// A plugin that panics while it starts.
+struct Broken;
+
+#[async_trait]
+impl ManagedPlugin for Broken {
+    fn new(_: &mut Plugins) -> Arc<Self> {
+        Arc::new(Self)
+    }
+
+    async fn start(&self) {
+        panic!("disk full");
+    }
+
+    fn span(&self) -> Span {
+        info_span!("broken")
+    }
+}
+
+/// A plugin that depends on [`Broken`].
+struct Dependent;
+
+impl ManagedPlugin for Dependent {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        plugins.load::<Broken>();
+        Arc::new(Self)
+    }
+
+    fn span(&self) -> Span {
+        info_span!("dependent")
+    }
+}
+
+#[tokio::test]
+async fn test_failed_start() {
+    let mut plugins = plugins();
+    plugins.load::<Dependent>();
+    let failures = failures(&plugins);
+
+    plugins.start().await;
+    assert_eq!(
+        plugins.state(1),
+        PluginState::Failed("disk full".to_string())
+    );
+    // plugins are not started if their dependencies failed
+    assert_eq!(plugins.state(2), PluginState::Loaded);
+    assert_eq!(
+        *failures.lock().unwrap(),
+        ["plugin `Broken` failed: disk full"]
+    );
+    assert_eq!(
+        plugins.health(),
+        Health::Failed("Broken: disk full".to_string())
+    );
+
+    // failed plugins stay failed
+    plugins.shutdown().await;
+    assert!(matches!(plugins.state(1), PluginState::Failed(_)));
+    assert_eq!(plugins.state(2), PluginState::Stopped);
+}
+
+/// A plugin whose worker panics once it is started.
+struct Crashing {
+    worker_failures: Event<WorkerFailure>,
+    span: Span,
+}
+
+#[async_trait]
+impl ManagedPlugin for Crashing {
+    fn new(plugins: &mut Plugins) -> Arc<Self> {
+        Arc::new(Self {
+            worker_failures: plugins.worker_failures(),
+            span: info_span!("crashing"),
+        })
+    }
+
+    async fn start(&self) {
+        let worker = traced::worker(
+            async { panic!("lost connection") },
+            info_span!(parent: self.span.clone(), "receiver"),
+            &self.worker_failures,
+        );
+        let _ = worker.await;
+    }
+
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+#[tokio::test]
+async fn test_worker_failures() {
+    let mut plugins = plugins();
+    plugins.load::<Crashing>();
+    let failures = failures(&plugins);
+
+    plugins.start().await;
+    // the worker is named after its span, which is disabled without a subscriber
+    let PluginState::Failed(reason) = plugins.state(1) else {
+        panic!("worker failure did not fail the plugin");
+    };
+    assert!(reason.ends_with("panicked: lost connection"));
+    assert_eq!(
+        *failures.lock().unwrap(),
+        [format!("plugin `Crashing` failed: {reason}")]
+    );
+    assert_eq!(
+        plugins.health(),
+        Health::Failed(format!("Crashing: {reason}"))
+    );
+}
+
+/// A plugin that reports that it is degraded.
+struct Lagging;
+
+impl ManagedPlugin for Lagging {
+    fn new(_: &mut Plugins) -> Arc<Self> {
+        Arc::new(Self)
+    }
+
+    fn health(&self) -> Health {
+        Health::Degraded("behind by 3 slots".to_string())
+    }
+
+    fn span(&self) -> Span {
+        info_span!("lagging")
+    }
+}
+
+#[tokio::test]
+async fn test_health() {
+    let mut plugins = plugins();
+    plugins.load::<Storage>();
+    assert!(plugins.health().is_healthy());
+
+    plugins.load::<Lagging>();
+    assert_eq!(
+        plugins.health(),
+        Health::Degraded("Lagging: behind by 3 slots".to_string())
+    );
+
+    // failures are worse than degradations
+    plugins.load::<Broken>();
+    plugins.start().await;
+    assert_eq!(
+        plugins.health(),
+        Health::Failed("Broken: disk full".to_string())
+    );
+}
@@ -1,4 +1,8 @@
-use common::ids::IssuerID;
+use std::sync::Arc;
+
+use clock::{Clock, SystemClock};
+use common::crypto::SigningKey;
+use config::Config;
 use networking::Networking;
 use sim::{Network, Node};
 use tracing::info_span;
@@ -12,11 +16,34 @@ async fn test_protocol() {
         .with_test_writer()
         .try_init();
 
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let genesis_time = clock.now();
+
     let nodes = [
-        Node::new_validator(info_span!("node1"), IssuerID::from([1; 32])),
-        Node::new_validator(info_span!("node2"), IssuerID::from([2; 32])),
-        Node::new_validator(info_span!("node3"), IssuerID::from([3; 32])),
-        Node::new_validator(info_span!("node4"), IssuerID::from([4; 32])),
+        Node::new_validator(
+            info_span!("node1"),
+            SigningKey::from([1; 32]),
+            clock.clone(),
+            genesis_time,
+        ),
+        Node::new_validator(
+            info_span!("node2"),
+            SigningKey::from([2; 32]),
+            clock.clone(),
+            genesis_time,
+        ),
+        Node::new_validator(
+            info_span!("node3"),
+            SigningKey::from([3; 32]),
+            clock.clone(),
+            genesis_time,
+        ),
+        Node::new_validator(
+            info_span!("node4"),
+            SigningKey::from([4; 32]),
+            clock.clone(),
+            genesis_time,
+        ),
     ];
 
     let network = Network::default();
@@ -25,7 +52,7 @@ async fn test_protocol() {
     for node in nodes {
         let _ = node
             .plugins
-            .get::<Networking>()
+            .get::<Networking<Config>>()
             .unwrap()
             .connect(&network)
             .await;
@@ -0,0 +1,67 @@
+use std::{sync::Arc, time::Duration};
+
+use block_storage::BlockStorage;
+use clock::{Clock, SystemClock};
+use common::{crypto::SigningKey, errors::Error};
+use config::Config;
+use networking::Networking;
+use pruning::PruningConfigParams;
+use sim::{Network, Node};
+use tracing::info_span;
+
+#[tokio::test]
+async fn test_pruning_bounds_block_storage() {
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let genesis_time = clock.now();
+
+    let network = Network::default();
+    let mut nodes = Vec::new();
+    for i in 1..=4u8 {
+        let node = Node::new_validator(
+            info_span!("node", id = i),
+            SigningKey::from([i; 32]),
+            clock.clone(),
+            genesis_time,
+        );
+        let _ = node
+            .plugins
+            .get::<Networking<Config>>()
+            .unwrap()
+            .connect(&network)
+            .await;
+        node.start().await;
+        nodes.push(node);
+    }
+
+    // remember an early block, which falls out of the retained heights once the nodes progress
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let block_storage = nodes[0].plugins.get::<BlockStorage>().unwrap();
+    let old_block = block_storage
+        .blocks()
+        .into_iter()
+        .find(|block| !block.block.parents().is_empty())
+        .expect("nodes should have issued blocks")
+        .block
+        .id()
+        .clone();
+
+    tokio::time::sleep(Duration::from_secs(3)).await;
+
+    assert!(block_storage.get(&old_block).is_none());
+    assert!(matches!(
+        block_storage.address(&old_block),
+        Err(Error::BlockPruned { ref block_id, .. }) if *block_id == old_block
+    ));
+
+    // every validator issues one block per round, so the storage should never hold much more than
+    // the blocks of the retained heights
+    let max_size = 4 * (PruningConfigParams::default().depth as usize + 16);
+    for node in &nodes {
+        let size = node.plugins.get::<BlockStorage>().unwrap().size();
+        assert!(size <= max_size, "block storage grew to {size} blocks");
+    }
+
+    for node in &nodes {
+        node.shutdown().await;
+    }
+}
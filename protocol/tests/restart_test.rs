@@ -0,0 +1,110 @@
+use std::{sync::Arc, time::Duration};
+
+use block_storage::{BlockStore, DiskBlockStore};
+use clock::{Clock, SystemClock};
+use common::{
+    crypto::SigningKey,
+    ids::{BlockID, Id},
+};
+use config::{Config, ProtocolParams};
+use consensus::Consensus;
+use networking::Networking;
+use protocol::Protocol;
+use sim::{Network, Node};
+use tracing::info_span;
+
+#[tokio::test]
+async fn test_restart_from_disk() {
+    let path = std::env::temp_dir().join(format!("restart-test-{}.redb", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let genesis_time = clock.now();
+
+    let accepted_milestones = {
+        let store: Arc<dyn BlockStore> = Arc::new(DiskBlockStore::open(&path).unwrap());
+
+        let network = Network::default();
+        let mut nodes = Vec::new();
+        for i in 1..=4u8 {
+            let span = info_span!("node", id = i);
+            let node = match i {
+                1 => Node::new_validator_with_store(
+                    span,
+                    SigningKey::from([i; 32]),
+                    clock.clone(),
+                    genesis_time,
+                    store.clone(),
+                ),
+                _ => Node::new_validator(
+                    span,
+                    SigningKey::from([i; 32]),
+                    clock.clone(),
+                    genesis_time,
+                ),
+            };
+            let _ = node
+                .plugins
+                .get::<Networking<Config>>()
+                .unwrap()
+                .connect(&network)
+                .await;
+            node.start().await;
+            nodes.push(node);
+        }
+
+        // wait for the first node to accept a few milestones instead of a fixed time
+        let store = store.clone();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while store.accepted_milestones().unwrap().len() < 3 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("the first node should accept milestones");
+
+        for node in &nodes {
+            node.shutdown().await;
+        }
+
+        // the stored acceptance is final once all nodes are stopped
+        store.accepted_milestones().unwrap()
+    };
+
+    let store: Arc<dyn BlockStore> = Arc::new(DiskBlockStore::open(&path).unwrap());
+    let restarted = Protocol::new(
+        Config::default()
+            .with_protocol_params(
+                ProtocolParams::default()
+                    .with_clock(clock.clone())
+                    .with_block_store(store.clone()),
+            )
+            .with_genesis_time(genesis_time),
+    );
+    restarted.start().await;
+
+    // replaying the stored blocks leads back to the stored acceptance, extended by the blocks
+    // that were stored while the node shut down
+    let replayed = store.accepted_milestones().unwrap();
+    assert!(replayed.starts_with(&accepted_milestones));
+
+    let (_, last_milestone) = replayed.last().unwrap();
+    assert_ne!(*last_milestone, Id::default());
+    assert_eq!(
+        latest_accepted_milestone(&restarted).as_ref(),
+        Some(last_milestone)
+    );
+
+    restarted.shutdown().await;
+    drop(restarted);
+    drop(store);
+    let _ = std::fs::remove_file(&path);
+}
+
+fn latest_accepted_milestone(protocol: &Protocol) -> Option<BlockID> {
+    let consensus = protocol.plugins.get::<Consensus<Config>>().unwrap();
+    let vote = consensus.latest_accepted_milestone.get();
+    vote.as_ref()
+        .and_then(|vote| vote.source.try_upgrade().ok())
+        .map(|block| block.block.id().clone())
+}
@@ -0,0 +1,121 @@
+use std::{sync::Arc, time::Duration};
+
+use block_storage::BlockStorage;
+use clock::{Clock, SystemClock};
+use common::{
+    crypto::SigningKey,
+    networking::{Message, Network as _, Recipients},
+};
+use config::{Config, ProtocolParams};
+use consensus::Consensus;
+use networking::Networking;
+use protocol::Protocol;
+use sim::{Network, Node};
+use snapshot::Snapshot;
+use tracing::info_span;
+use virtual_voting::VirtualVotingConfig;
+
+#[tokio::test]
+async fn test_bootstrap_from_snapshot() {
+    let path = std::env::temp_dir().join(format!("snapshot-test-{}.snapshot", std::process::id()));
+
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let genesis_time = clock.now();
+
+    let network = Network::default();
+    let mut nodes = Vec::new();
+    for i in 1..=4u8 {
+        let node = Node::new_validator(
+            info_span!("node", id = i),
+            SigningKey::from([i; 32]),
+            clock.clone(),
+            genesis_time,
+        );
+        let _ = node
+            .plugins
+            .get::<Networking<Config>>()
+            .unwrap()
+            .connect(&network)
+            .await;
+        node.start().await;
+        nodes.push(node);
+    }
+
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // stop the peers first so that the accepted milestone of the first node settles
+    for node in nodes.iter().skip(1) {
+        node.shutdown().await;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let config = nodes[0].plugins.get::<Config>().unwrap();
+    let consensus = nodes[0].plugins.get::<Consensus<Config>>().unwrap();
+    let block_storage = nodes[0].plugins.get::<BlockStorage>().unwrap();
+    let snapshot = Snapshot::take(&*config, &consensus, &block_storage).unwrap();
+    let stored_blocks = block_storage.size();
+    nodes[0].shutdown().await;
+
+    assert!(snapshot.confirmed.height > 0);
+    assert!(snapshot.blocks.len() < stored_blocks);
+
+    snapshot.write(&path).unwrap();
+    let registry = snapshot.registry.clone();
+    let snapshot = Snapshot::read(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(snapshot.registry, registry);
+    for member in Config::default().select_committee(None).unwrap().iter() {
+        assert_eq!(snapshot.registry.stake(member.id()), member.weight());
+    }
+
+    let (confirmed, accepted) = (snapshot.confirmed.clone(), snapshot.accepted.clone());
+    let unfinalized = snapshot.blocks.first().unwrap().id().clone();
+    let restarted = Protocol::new(
+        Config::default()
+            .with_protocol_params(
+                ProtocolParams::default()
+                    .with_clock(clock.clone())
+                    .with_snapshot(snapshot),
+            )
+            .with_genesis_time(genesis_time),
+    );
+    restarted.start().await;
+
+    // votes above the confirmed milestone are rebuilt from it, so the node accepts at least up to
+    // the height that was accepted when the snapshot was taken
+    let consensus = restarted.plugins.get::<Consensus<Config>>().unwrap();
+    let latest_accepted_milestone = consensus.latest_accepted_milestone.get().clone().unwrap();
+    assert!(latest_accepted_milestone.height().unwrap() >= accepted.height);
+
+    let block_storage = restarted.plugins.get::<BlockStorage>().unwrap();
+    let confirmed_block = block_storage.get(&confirmed.block_id).unwrap();
+    assert!(consensus.is_accepted(&confirmed_block).unwrap());
+
+    // the roots of the snapshot are local to the node, so requests for them are not answered
+    let network = Network::default();
+    restarted
+        .plugins
+        .get::<Networking<Config>>()
+        .unwrap()
+        .connect(&network)
+        .await;
+    let mut peer = network.endpoint().await;
+    for block_id in [&confirmed.block_id, &unfinalized] {
+        peer.outbound
+            .send((Recipients::All, Message::BlockRequest(block_id.clone())))
+            .unwrap();
+    }
+
+    let mut responses = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_millis(200), async {
+        while let Some((_, message)) = peer.inbound.recv().await {
+            if let Message::BlockResponse(block) = message {
+                responses.push(block.id().clone());
+            }
+        }
+    })
+    .await;
+    assert_eq!(responses, vec![unfinalized]);
+
+    restarted.shutdown().await;
+}
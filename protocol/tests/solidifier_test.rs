@@ -0,0 +1,143 @@
+use std::{sync::Arc, time::Duration};
+
+use block_storage::BlockStorage;
+use clock::{Clock, SystemClock};
+use common::{
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::{BlockID, Id},
+    networking::{Endpoint, Message, Network as _},
+};
+use config::{Config, ProtocolParams, ProtocolPlugins};
+use consensus::Consensus;
+use networking::Networking;
+use protocol::{Protocol, ProtocolConfig};
+use sim::{Network, Node};
+use solidifier::{Solidifier, SolidifierConfigParams};
+use tracing::info_span;
+
+#[tokio::test]
+async fn test_late_node_requests_missing_blocks() {
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let genesis_time = clock.now();
+
+    let network = Network::default();
+    let mut nodes = Vec::new();
+    for i in 1..=4u8 {
+        let node = Node::new_validator(
+            info_span!("node", id = i),
+            SigningKey::from([i; 32]),
+            clock.clone(),
+            genesis_time,
+        );
+        let _ = node
+            .plugins
+            .get::<Networking<Config>>()
+            .unwrap()
+            .connect(&network)
+            .await;
+        node.start().await;
+        nodes.push(node);
+    }
+
+    // let the validators build some history that the late node has to request
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let late = Node::new(info_span!("late"), || {
+        Config::default()
+            .with_protocol_params(
+                ProtocolParams::default()
+                    .with_plugins(ProtocolPlugins::core().with::<Solidifier<Config>>())
+                    .with_clock(clock.clone()),
+            )
+            .with_genesis_time(genesis_time)
+    });
+    late.plugins
+        .get::<Networking<Config>>()
+        .unwrap()
+        .connect(&network)
+        .await;
+    late.start().await;
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    // the late node only sees the blocks issued after it connected, so it can only accept
+    // milestones if it requested their past cone down to genesis
+    let late_height = accepted_height(&late);
+    let validator_height = accepted_height(&nodes[0]);
+    assert!(
+        late_height > validator_height / 2,
+        "late node fell behind (height={late_height}, validator height={validator_height})"
+    );
+
+    late.shutdown().await;
+    for node in &nodes {
+        node.shutdown().await;
+    }
+}
+
+#[tokio::test]
+async fn test_given_up_blocks_are_requested_from_new_peers() {
+    let node = Protocol::new(
+        Config::default()
+            .with_protocol_params(
+                ProtocolParams::default()
+                    .with_plugins(ProtocolPlugins::core().with::<Solidifier<Config>>()),
+            )
+            .with_params(SolidifierConfigParams {
+                check_interval: 10,
+                request_timeout: 20,
+                max_attempts: 2,
+            }),
+    );
+    let network = Network::default();
+    node.plugins
+        .get::<Networking<Config>>()
+        .unwrap()
+        .connect(&network)
+        .await;
+    node.start().await;
+
+    // a peer that never answers, so that the node gives up on the missing parent
+    let mut first = network.endpoint().await;
+    let missing = Id::from([7; 32]);
+    let child = Block::from(NetworkBlock::new(
+        vec![missing.clone()],
+        1,
+        Vec::new(),
+        &SigningKey::from([1; 32]),
+    ));
+    node.plugins.get::<BlockStorage>().unwrap().insert(child);
+
+    assert_eq!(requests(&mut first, &missing, 300).await, 2);
+
+    // a new peer might know the block, so it is requested again
+    let mut second = network.endpoint().await;
+    assert!(requests(&mut second, &missing, 300).await > 0);
+
+    node.shutdown().await;
+}
+
+/// Counts the requests for the given block that the endpoint receives within the given time (in
+/// milliseconds).
+async fn requests(endpoint: &mut Endpoint, block_id: &BlockID, millis: u64) -> usize {
+    let mut count = 0;
+    let _ = tokio::time::timeout(Duration::from_millis(millis), async {
+        while let Some((_, message)) = endpoint.inbound.recv().await {
+            if matches!(message, Message::BlockRequest(ref id) if id == block_id) {
+                count += 1;
+            }
+        }
+    })
+    .await;
+
+    count
+}
+
+fn accepted_height(protocol: &Protocol) -> u64 {
+    let consensus = protocol.plugins.get::<Consensus<Config>>().unwrap();
+    let vote = consensus.latest_accepted_milestone.get();
+    vote.as_ref()
+        .and_then(|vote| vote.height().ok())
+        .unwrap_or(0)
+}
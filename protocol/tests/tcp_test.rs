@@ -0,0 +1,111 @@
+use std::{sync::Arc, time::Duration};
+
+use clock::{Clock, SystemClock};
+use common::{crypto::SigningKey, networking::Network as _};
+use config::Config;
+use consensus::Consensus;
+use networking::Networking;
+use protocol::Protocol;
+use sim::Node;
+use tcp::{ConnectionState, TcpConfig, TcpNetwork};
+use tokio::net::TcpListener;
+use tracing::info_span;
+use virtual_voting::VirtualVotingConfig;
+
+#[tokio::test]
+async fn test_authenticated_validators_over_tcp() {
+    let clock: Arc<dyn Clock> = Arc::new(SystemClock);
+    let genesis_time = clock.now();
+
+    let mut listeners = Vec::new();
+    for _ in 0..4 {
+        listeners.push(TcpListener::bind("127.0.0.1:0").await.unwrap());
+    }
+    let addresses: Vec<_> = listeners
+        .iter()
+        .map(|listener| listener.local_addr().unwrap())
+        .collect();
+
+    let committee = Config::default().select_committee(None).unwrap();
+
+    // a peer outside of the committee that the first node tries to connect to
+    let intruder = TcpNetwork::bind(
+        SigningKey::from([42; 32]),
+        committee.clone(),
+        TcpConfig::default(),
+    )
+    .await
+    .unwrap();
+    let intruder_address = intruder.local_addr().unwrap();
+    let _intruder_endpoint = intruder.endpoint().await;
+
+    let mut networks = Vec::new();
+    for (i, listener) in listeners.into_iter().enumerate() {
+        networks.push(TcpNetwork::with_listener(
+            listener,
+            SigningKey::from([i as u8 + 1; 32]),
+            committee.clone(),
+            TcpConfig {
+                peers: addresses
+                    .iter()
+                    .filter(|address| **address != addresses[i])
+                    .copied()
+                    .chain((i == 0).then_some(intruder_address))
+                    .collect(),
+                ..Default::default()
+            },
+        ));
+    }
+
+    let mut nodes = Vec::new();
+    for (i, network) in networks.iter().enumerate() {
+        let node = Node::new_validator(
+            info_span!("node", id = i + 1),
+            SigningKey::from([i as u8 + 1; 32]),
+            clock.clone(),
+            genesis_time,
+        );
+        node.plugins
+            .get::<Networking<Config>>()
+            .unwrap()
+            .connect(network)
+            .await;
+        network.follow_committee(&node.plugins.get::<Consensus<Config>>().unwrap().committee);
+        nodes.push(node);
+    }
+    for node in &nodes {
+        node.start().await;
+    }
+
+    // rejected peers are retried like disconnected ones, so the state of the intruder alternates
+    let mut intruder_rejected = false;
+    for _ in 0..100 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        intruder_rejected |=
+            networks[0].connection_state(&intruder_address) == Some(ConnectionState::Rejected);
+    }
+
+    for network in &networks {
+        for (peer, state) in network.connections() {
+            if peer != intruder_address {
+                assert_eq!(state, ConnectionState::Connected, "not connected to {peer}");
+            }
+        }
+    }
+    assert!(intruder_rejected, "intruder was not rejected");
+    for node in &nodes {
+        assert!(accepted_height(node) > 0, "no milestone accepted over tcp");
+    }
+
+    for node in &nodes {
+        node.shutdown().await;
+    }
+}
+
+fn accepted_height(protocol: &Protocol) -> u64 {
+    let consensus = protocol.plugins.get::<Consensus<Config>>().unwrap();
+    let vote = consensus.latest_accepted_milestone.get();
+    vote.as_ref()
+        .and_then(|vote| vote.height().ok())
+        .unwrap_or(0)
+}
@@ -1,57 +1,88 @@
 use std::sync::{
     Arc,
-    atomic::{AtomicUsize, Ordering},
+    atomic::{AtomicU64, Ordering},
 };
 
 use async_trait::async_trait;
-use common::{blocks::Block, networking, networking::Endpoint};
+use common::{
+    networking,
+    networking::{Endpoint, Message, PeerID, Recipients},
+};
 use tokio::sync::{
     Mutex,
     mpsc::{UnboundedSender, unbounded_channel},
+    watch,
 };
 use tracing::trace;
 
-type NodeId = usize;
+type Peers = Vec<Peer>;
+
+struct Peer {
+    id: PeerID,
+    inbound: UnboundedSender<(PeerID, Message)>,
+    peers: watch::Sender<Vec<PeerID>>,
+}
 
 #[derive(Default)]
 pub struct Network {
-    next_id: AtomicUsize,
-    nodes: Arc<Mutex<Vec<(NodeId, UnboundedSender<Block>)>>>,
+    next_id: AtomicU64,
+    nodes: Arc<Mutex<Peers>>,
 }
 
 #[async_trait]
 impl networking::Network for Network {
     async fn endpoint(&self) -> Endpoint {
-        let (tx_inbound, rx_inbound) = unbounded_channel::<Block>();
-        let node_id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx_inbound, rx_inbound) = unbounded_channel::<(PeerID, Message)>();
+        let (tx_peers, rx_peers) = watch::channel(Vec::new());
+        let node_id = PeerID(self.next_id.fetch_add(1, Ordering::Relaxed));
         {
             let mut nodes = self.nodes.lock().await;
-            nodes.push((node_id, tx_inbound.clone()));
+            nodes.push(Peer {
+                id: node_id,
+                inbound: tx_inbound,
+                peers: tx_peers,
+            });
+            publish_peers(&nodes);
         }
 
         let nodes = self.nodes.clone();
 
-        let (tx_outbound, mut rx_outbound) = unbounded_channel::<Block>();
+        let (tx_outbound, mut rx_outbound) = unbounded_channel::<(Recipients, Message)>();
         tokio::spawn(async move {
-            while let Some(block) = rx_outbound.recv().await {
-                let nodes = nodes.lock().await;
-                for (peer_id, peer_tx) in nodes.iter() {
-                    if *peer_id != node_id {
-                        trace!(
-                            "Sending block {} from peer {} to peer {}",
-                            block.id(),
-                            node_id,
-                            peer_id
-                        );
-                        let _ = peer_tx.send(block.clone()); // ignore send failures
+            while let Some((recipients, message)) = rx_outbound.recv().await {
+                let mut nodes = nodes.lock().await;
+                for peer in nodes.iter() {
+                    if peer.id != node_id && recipients.includes(&peer.id) {
+                        trace!("Sending message from {} to {}", node_id, peer.id);
+                        let _ = peer.inbound.send((node_id, message.clone())); // ignore send failures
                     }
                 }
+
+                // forget the nodes whose endpoint was dropped
+                let count = nodes.len();
+                nodes.retain(|peer| !peer.inbound.is_closed());
+                if nodes.len() != count {
+                    publish_peers(&nodes);
+                }
             }
         });
 
         Endpoint {
             inbound: rx_inbound,
             outbound: tx_outbound,
+            peers: rx_peers,
         }
     }
 }
+
+/// Updates the peers of every node to the other nodes of the network.
+fn publish_peers(nodes: &Peers) {
+    for peer in nodes {
+        let others = nodes
+            .iter()
+            .map(|other| other.id)
+            .filter(|id| *id != peer.id)
+            .collect();
+        peer.peers.send_replace(others);
+    }
+}
@@ -1,10 +1,12 @@
 use std::{ops::Deref, sync::Arc};
 
-use common::ids::IssuerID;
+use block_storage::BlockStore;
+use clock::{Clock, ProtocolClock};
+use common::crypto::SigningKey;
 use config::{Config, ProtocolParams, ProtocolPlugins};
 use protocol::{Protocol, ProtocolConfig};
 use tracing::{Instrument, Span};
-use validator::{Validator, ValidatorConfigParams};
+use validator::ValidatorConfigParams;
 
 pub struct Node {
     pub protocol: Arc<Protocol>,
@@ -19,24 +21,58 @@ impl Node {
         }
     }
 
-    pub fn new_validator(span: Span, issuer_id: IssuerID) -> Self {
+    pub fn new_validator(
+        span: Span,
+        signing_key: SigningKey,
+        clock: Arc<dyn Clock>,
+        genesis_time: u64,
+    ) -> Self {
+        Self::new_validator_with_params(span, signing_key, clock, genesis_time, || {
+            ProtocolParams::default()
+        })
+    }
+
+    pub fn new_validator_with_store(
+        span: Span,
+        signing_key: SigningKey,
+        clock: Arc<dyn Clock>,
+        genesis_time: u64,
+        block_store: Arc<dyn BlockStore>,
+    ) -> Self {
+        Self::new_validator_with_params(span, signing_key, clock, genesis_time, move || {
+            ProtocolParams::default().with_block_store(block_store.clone())
+        })
+    }
+
+    fn new_validator_with_params(
+        span: Span,
+        signing_key: SigningKey,
+        clock: Arc<dyn Clock>,
+        genesis_time: u64,
+        params: impl Fn() -> ProtocolParams,
+    ) -> Self {
         Self::new(span, move || {
             Config::default()
-                .with_protocol_params(ProtocolParams::default().with_plugins(
-                    ProtocolPlugins::Custom(|cfg, registry| {
-                        ProtocolPlugins::Core.inject(cfg, registry);
-                        registry.load::<Validator<Config>>();
-                    }),
-                ))
+                .with_protocol_params(
+                    params()
+                        .with_plugins(ProtocolPlugins::validator())
+                        .with_clock(clock.clone()),
+                )
+                .with_genesis_time(genesis_time)
                 .with_params(ValidatorConfigParams {
-                    validator_id: issuer_id.clone(),
+                    signing_key: signing_key.clone(),
                 })
         })
     }
 
+    /// Runs the node until the given duration passed on its clock.
     pub async fn run_for(self, duration: std::time::Duration) {
         self.start().instrument(self.span.clone()).await;
-        tokio::time::sleep(duration).await;
+        self.plugins
+            .get::<ProtocolClock>()
+            .unwrap()
+            .sleep(duration)
+            .await;
         self.shutdown().instrument(self.span.clone()).await;
     }
 }
@@ -0,0 +1,73 @@
+use std::{collections::HashSet, net::SocketAddr};
+
+use common::{bft::Committee, ids::IssuerID};
+use tokio::sync::watch;
+use tracing::warn;
+
+/// The name of the counter that is incremented for every rejected peer, labeled with the `reason`
+/// of the [`Rejection`].
+pub const REJECTED_PEERS_METRIC: &str = "tcp_rejected_peers";
+
+/// Decides which authenticated peers may exchange messages with the node: the members of the
+/// current committee and the allowlisted observers.
+pub(crate) struct Admission {
+    committee: watch::Sender<Committee>,
+    observers: HashSet<IssuerID>,
+}
+
+impl Admission {
+    pub(crate) fn new(committee: Committee, observers: impl IntoIterator<Item = IssuerID>) -> Self {
+        Self {
+            committee: watch::Sender::new(committee),
+            observers: observers.into_iter().collect(),
+        }
+    }
+
+    pub(crate) fn admits(&self, issuer: &IssuerID) -> bool {
+        self.observers.contains(issuer) || self.committee.borrow().member(issuer).is_some()
+    }
+
+    pub(crate) fn set_committee(&self, committee: Committee) {
+        self.committee.send_replace(committee);
+    }
+
+    /// Resolves once the issuer is no longer admitted after a committee change.
+    pub(crate) async fn evicted(&self, issuer: &IssuerID) {
+        let mut changes = self.committee.subscribe();
+        while changes.changed().await.is_ok() {
+            if !self.admits(issuer) {
+                return;
+            }
+        }
+    }
+}
+
+/// The reason for rejecting a peer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Rejection {
+    /// The peer did not prove that it owns the issuer key it claimed.
+    InvalidIdentity,
+    /// The peer is neither a committee member nor an allowlisted observer.
+    NotAdmitted,
+    /// The peer was a committee member when it connected but is not part of the new committee.
+    LeftCommittee,
+}
+
+impl Rejection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rejection::InvalidIdentity => "invalid_identity",
+            Rejection::NotAdmitted => "not_admitted",
+            Rejection::LeftCommittee => "left_committee",
+        }
+    }
+
+    /// Reports the rejection of the peer at the given address.
+    pub(crate) fn report(self, address: &SocketAddr, issuer: Option<&IssuerID>) {
+        match issuer {
+            Some(issuer) => warn!(%address, %issuer, reason = self.as_str(), "rejected peer"),
+            None => warn!(%address, reason = self.as_str(), "rejected peer"),
+        }
+        metrics::counter!(REJECTED_PEERS_METRIC, "reason" => self.as_str()).increment(1);
+    }
+}
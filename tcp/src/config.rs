@@ -0,0 +1,32 @@
+use std::{net::SocketAddr, time::Duration};
+
+use common::ids::IssuerID;
+
+pub struct TcpConfig {
+    /// The address to accept connections from peers on.
+    pub listen_address: SocketAddr,
+    /// The addresses of the peers to connect to.
+    pub peers: Vec<SocketAddr>,
+    /// The issuers of observer nodes that are admitted in addition to the committee members.
+    pub observers: Vec<IssuerID>,
+    /// The delay before the first reconnection attempt after a connection failed.
+    pub min_backoff: Duration,
+    /// The upper bound for the delay between reconnection attempts, which doubles after every
+    /// failed attempt.
+    pub max_backoff: Duration,
+    /// The time a peer has to complete the handshake before the connection is closed.
+    pub handshake_timeout: Duration,
+}
+
+impl Default for TcpConfig {
+    fn default() -> Self {
+        Self {
+            listen_address: SocketAddr::from(([127, 0, 0, 1], 0)),
+            peers: Vec::new(),
+            observers: Vec::new(),
+            min_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            handshake_timeout: Duration::from_secs(5),
+        }
+    }
+}
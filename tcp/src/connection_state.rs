@@ -0,0 +1,14 @@
+/// The state of the connection to a peer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConnectionState {
+    /// A connection attempt is in progress.
+    Connecting,
+    /// The connection is established and messages are sent to the peer.
+    Connected,
+    /// The last connection attempt failed or the connection was lost. Messages for the peer are
+    /// dropped until it is reconnected.
+    Disconnected,
+    /// The peer failed to authenticate or is not admitted (anymore). It is retried like a
+    /// disconnected peer, as it might be admitted after the next committee change.
+    Rejected,
+}
@@ -0,0 +1,47 @@
+use std::io;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// The maximum length of a frame, which protects peers from allocating arbitrary amounts of memory.
+pub(crate) const MAX_FRAME_LENGTH: usize = 16 * 1024 * 1024;
+
+/// The maximum length of a frame during the handshake. Handshake messages are much smaller, and
+/// the peer is not authenticated yet, so it must not be able to make the node allocate more.
+pub(crate) const MAX_HANDSHAKE_FRAME_LENGTH: usize = 1024;
+
+/// Writes the payload prefixed with its length as a big-endian `u32`.
+pub(crate) async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    payload: &[u8],
+) -> io::Result<()> {
+    if payload.len() > MAX_FRAME_LENGTH {
+        return Err(frame_too_long(payload.len(), MAX_FRAME_LENGTH));
+    }
+
+    let mut frame = Vec::with_capacity(4 + payload.len());
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame).await
+}
+
+/// Reads a payload written by [`write_frame`], failing if it is longer than `max_length`.
+pub(crate) async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    max_length: usize,
+) -> io::Result<Vec<u8>> {
+    let length = reader.read_u32().await? as usize;
+    if length > max_length {
+        return Err(frame_too_long(length, max_length));
+    }
+
+    let mut payload = vec![0; length];
+    reader.read_exact(&mut payload).await?;
+    Ok(payload)
+}
+
+fn frame_too_long(length: usize, max_length: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("frame of {length} bytes exceeds the maximum of {max_length} bytes"),
+    )
+}
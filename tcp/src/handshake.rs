@@ -0,0 +1,121 @@
+use std::io;
+
+use common::{
+    crypto::{PublicKey, Signature, SigningKey},
+    ids::IssuerID,
+};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::{
+    frame::{MAX_HANDSHAKE_FRAME_LENGTH, read_frame, write_frame},
+    session::{MAX_NOISE_MESSAGE, Session, invalid_data},
+};
+
+/// The Noise protocol that establishes the session keys. It only uses ephemeral keys, as peers
+/// authenticate with their issuer keys after the handshake.
+const NOISE_PARAMS: &str = "Noise_NN_25519_ChaChaPoly_BLAKE2s";
+
+/// The domain separator of the signatures that bind the issuer keys to a session.
+const IDENTITY_CONTEXT: &[u8] = b"idealism/tcp/identity";
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub(crate) enum Role {
+    Initiator,
+    Responder,
+}
+
+pub(crate) enum HandshakeError {
+    Io(io::Error),
+    /// The peer sent a malformed identity or a signature that does not match its issuer key.
+    InvalidIdentity,
+}
+
+impl From<io::Error> for HandshakeError {
+    fn from(err: io::Error) -> Self {
+        HandshakeError::Io(err)
+    }
+}
+
+impl From<snow::Error> for HandshakeError {
+    fn from(err: snow::Error) -> Self {
+        HandshakeError::Io(invalid_data(err))
+    }
+}
+
+/// Establishes an encrypted session and returns it together with the authenticated issuer of the
+/// peer.
+///
+/// After the Noise handshake, both sides send their issuer ID and a signature of the handshake
+/// hash over the encrypted channel. As the hash is unique to the session, the signature proves
+/// that the peer owns the issuer key and cannot be replayed on another connection.
+pub(crate) async fn handshake<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut S,
+    role: Role,
+    signing_key: &SigningKey,
+) -> Result<(IssuerID, Session), HandshakeError> {
+    let builder = snow::Builder::new(NOISE_PARAMS.parse()?);
+    let mut noise = match role {
+        Role::Initiator => builder.build_initiator()?,
+        Role::Responder => builder.build_responder()?,
+    };
+
+    let mut buffer = vec![0; MAX_NOISE_MESSAGE];
+    if role == Role::Initiator {
+        let length = noise.write_message(&[], &mut buffer)?;
+        write_frame(stream, &buffer[..length]).await?;
+        noise.read_message(
+            &read_frame(stream, MAX_HANDSHAKE_FRAME_LENGTH).await?,
+            &mut buffer,
+        )?;
+    } else {
+        noise.read_message(
+            &read_frame(stream, MAX_HANDSHAKE_FRAME_LENGTH).await?,
+            &mut buffer,
+        )?;
+        let length = noise.write_message(&[], &mut buffer)?;
+        write_frame(stream, &buffer[..length]).await?;
+    }
+
+    let handshake_hash = noise.get_handshake_hash().to_vec();
+    let mut session = Session::new(noise.into_transport_mode()?);
+
+    let signature = signing_key.sign(&identity_message(role, &handshake_hash));
+    let mut identity = signing_key.issuer_id().to_vec();
+    identity.extend_from_slice(&signature.to_bytes());
+    session.send(stream, &identity).await?;
+
+    let peer_role = match role {
+        Role::Initiator => Role::Responder,
+        Role::Responder => Role::Initiator,
+    };
+    let peer = verify_identity(
+        &session.receive(stream, MAX_HANDSHAKE_FRAME_LENGTH).await?,
+        &identity_message(peer_role, &handshake_hash),
+    )?;
+
+    Ok((peer, session))
+}
+
+fn verify_identity(identity: &[u8], message: &[u8]) -> Result<IssuerID, HandshakeError> {
+    let (Ok(issuer_id), Ok(signature)) = (
+        <[u8; 32]>::try_from(&identity[..identity.len().min(32)]),
+        <[u8; 64]>::try_from(&identity[identity.len().min(32)..]),
+    ) else {
+        return Err(HandshakeError::InvalidIdentity);
+    };
+
+    let issuer_id = IssuerID::from(issuer_id);
+    match PublicKey::try_from(&issuer_id) {
+        Ok(public_key) if public_key.verify(message, &Signature::from(signature)) => Ok(issuer_id),
+        _ => Err(HandshakeError::InvalidIdentity),
+    }
+}
+
+/// Returns the message that the side with the given role signs. The role is included so that a
+/// peer cannot reflect the signature of the node back to it.
+fn identity_message(role: Role, handshake_hash: &[u8]) -> Vec<u8> {
+    let mut message = IDENTITY_CONTEXT.to_vec();
+    message.push(role as u8);
+    message.extend_from_slice(handshake_hash);
+    message
+}
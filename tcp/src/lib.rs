@@ -0,0 +1,9 @@
+mod admission;
+mod config;
+mod connection_state;
+mod frame;
+mod handshake;
+mod network;
+mod session;
+
+pub use crate::{admission::*, config::*, connection_state::*, network::*};
@@ -0,0 +1,466 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use common::{
+    bft::Committee,
+    crypto::SigningKey,
+    ids::IssuerID,
+    networking::{self, Endpoint, Message, PeerID, Recipients},
+    rx::{Callbacks, Subscription, Variable},
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::{
+        mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+        watch,
+    },
+    time::{Instant, sleep_until, timeout},
+};
+use tracing::{Instrument, debug, info, info_span, trace, warn};
+
+use crate::{
+    Admission, ConnectionState, Rejection, TcpConfig,
+    frame::{MAX_FRAME_LENGTH, read_frame, write_frame},
+    handshake::{HandshakeError, Role, handshake},
+    session::Session,
+};
+
+/// A [`Network`](networking::Network) that exchanges messages with a static list of peers over
+/// TCP.
+///
+/// A connection to every configured peer is kept open, and reestablished with an exponential
+/// backoff whenever it fails. Peers that are not configured (e.g. observers) connect to the listen
+/// address instead. Messages are exchanged in both directions over either kind of connection.
+///
+/// Connections are encrypted and both sides authenticate with their issuer keys. Only members of
+/// the committee and the configured observers are admitted, and connections to peers that leave
+/// the committee are closed. The committee is the one the network was created with, until it
+/// [follows](TcpNetwork::follow_committee) the committee of the consensus.
+pub struct TcpNetwork {
+    listener: Arc<TcpListener>,
+    shared: Arc<Shared>,
+    committee_subscription: Mutex<Option<Subscription<Callbacks<CommitteeUpdate>>>>,
+}
+
+/// The state that the network shares with its connections.
+struct Shared {
+    config: TcpConfig,
+    signing_key: SigningKey,
+    admission: Admission,
+    connections: Mutex<HashMap<SocketAddr, ConnectionState>>,
+    /// The ids of the authenticated peers. Peers are identified by their issuer, so that messages
+    /// received over their connection can be answered over the connection to them.
+    peer_ids: Mutex<HashMap<IssuerID, PeerID>>,
+    /// The ids of the peers that the dialers are connected to.
+    connected: Mutex<HashMap<SocketAddr, PeerID>>,
+    /// The frames to send over the accepted connections, so that peers that are not dialed (e.g.
+    /// observers) can be sent messages as well.
+    sessions: Mutex<HashMap<PeerID, UnboundedSender<Arc<Vec<u8>>>>>,
+    peers: watch::Sender<Vec<PeerID>>,
+}
+
+impl Shared {
+    fn peer_id(&self, issuer: &IssuerID) -> PeerID {
+        let mut peer_ids = self.peer_ids.lock().unwrap();
+        let next_id = PeerID(peer_ids.len() as u64);
+        *peer_ids.entry(issuer.clone()).or_insert(next_id)
+    }
+
+    fn set_connected(&self, address: SocketAddr, peer: Option<PeerID>) {
+        {
+            let mut connected = self.connected.lock().unwrap();
+            match peer {
+                Some(peer) => connected.insert(address, peer),
+                None => connected.remove(&address),
+            };
+        }
+        self.publish_peers();
+    }
+
+    fn add_session(&self, peer: PeerID, frames: UnboundedSender<Arc<Vec<u8>>>) {
+        self.sessions.lock().unwrap().insert(peer, frames);
+        self.publish_peers();
+    }
+
+    /// Removes the session of the peer, unless it was replaced by a newer one.
+    fn remove_session(&self, peer: PeerID, frames: &UnboundedSender<Arc<Vec<u8>>>) {
+        {
+            let mut sessions = self.sessions.lock().unwrap();
+            if sessions.get(&peer).is_some_and(|s| s.same_channel(frames)) {
+                sessions.remove(&peer);
+            }
+        }
+        self.publish_peers();
+    }
+
+    /// Publishes the peers that can be reached over a dialed or an accepted connection.
+    fn publish_peers(&self) {
+        let mut peers: Vec<PeerID> = self.connected.lock().unwrap().values().copied().collect();
+        peers.extend(self.sessions.lock().unwrap().keys().copied());
+        peers.sort();
+        peers.dedup();
+        self.peers.send_replace(peers);
+    }
+}
+
+impl TcpNetwork {
+    pub async fn bind(
+        signing_key: SigningKey,
+        committee: Committee,
+        config: TcpConfig,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(config.listen_address).await?;
+        Ok(Self::with_listener(
+            listener,
+            signing_key,
+            committee,
+            config,
+        ))
+    }
+
+    /// Creates a network that accepts connections on an already bound listener. The listen
+    /// address of the config is ignored.
+    pub fn with_listener(
+        listener: TcpListener,
+        signing_key: SigningKey,
+        committee: Committee,
+        config: TcpConfig,
+    ) -> Self {
+        Self {
+            listener: Arc::new(listener),
+            shared: Arc::new(Shared {
+                admission: Admission::new(committee, config.observers.iter().cloned()),
+                config,
+                signing_key,
+                connections: Default::default(),
+                peer_ids: Default::default(),
+                connected: Default::default(),
+                sessions: Default::default(),
+                peers: watch::Sender::new(Vec::new()),
+            }),
+            committee_subscription: Mutex::new(None),
+        }
+    }
+
+    /// Admits the members of the given committee (usually `Consensus.committee`) from now on,
+    /// so that the peer set follows the committee changes.
+    pub fn follow_committee(&self, committee: &Variable<Committee>) {
+        let shared = self.shared.clone();
+        *self.committee_subscription.lock().unwrap() =
+            Some(committee.subscribe(move |(_, committee)| {
+                if let Some(committee) = committee {
+                    shared.admission.set_committee(committee.clone());
+                }
+            }));
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn connection_state(&self, peer: &SocketAddr) -> Option<ConnectionState> {
+        self.shared.connections.lock().unwrap().get(peer).copied()
+    }
+
+    pub fn connections(&self) -> Vec<(SocketAddr, ConnectionState)> {
+        let connections = self.shared.connections.lock().unwrap();
+        connections
+            .iter()
+            .map(|(peer, state)| (*peer, *state))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl networking::Network for TcpNetwork {
+    async fn endpoint(&self) -> Endpoint {
+        let (tx_inbound, rx_inbound) = unbounded_channel::<(PeerID, Message)>();
+        let (tx_outbound, rx_outbound) = unbounded_channel::<(Recipients, Message)>();
+
+        let mut dialers = Vec::new();
+        for peer in &self.shared.config.peers {
+            let (tx, rx) = unbounded_channel();
+            self.shared
+                .connections
+                .lock()
+                .unwrap()
+                .insert(*peer, ConnectionState::Disconnected);
+
+            tokio::spawn(
+                dial(*peer, rx, tx_inbound.clone(), self.shared.clone())
+                    .instrument(info_span!("dialer", %peer)),
+            );
+            dialers.push((*peer, tx));
+        }
+
+        tokio::spawn(broadcast(rx_outbound, dialers, self.shared.clone()));
+        tokio::spawn(
+            accept(self.listener.clone(), tx_inbound, self.shared.clone())
+                .instrument(info_span!("listener", address = ?self.listener.local_addr().ok())),
+        );
+
+        Endpoint {
+            inbound: rx_inbound,
+            outbound: tx_outbound,
+            peers: self.shared.peers.subscribe(),
+        }
+    }
+}
+
+/// Encodes every outbound message once and hands it to the connections of its recipients. Peers
+/// are sent messages over the connection that is dialed to them, or over the connection they
+/// established if they are not dialed. Messages for all peers are also queued by dialers that are
+/// still connecting. Returns (and thereby stops the dialers) once the endpoint is dropped.
+async fn broadcast(
+    mut outbound: UnboundedReceiver<(Recipients, Message)>,
+    dialers: Vec<(SocketAddr, UnboundedSender<Arc<Vec<u8>>>)>,
+    shared: Arc<Shared>,
+) {
+    while let Some((recipients, message)) = outbound.recv().await {
+        let bytes = Arc::new(message.to_bytes());
+
+        let mut dialed = HashSet::new();
+        {
+            let connected = shared.connected.lock().unwrap();
+            for (address, dialer) in &dialers {
+                let send = match connected.get(address) {
+                    Some(peer) => dialed.insert(*peer) && recipients.includes(peer),
+                    None => recipients == Recipients::All,
+                };
+                if send {
+                    let _ = dialer.send(bytes.clone()); // ignore stopped dialers
+                }
+            }
+        }
+
+        let sessions = shared.sessions.lock().unwrap();
+        for (peer, session) in sessions.iter() {
+            if !dialed.contains(peer) && recipients.includes(peer) {
+                let _ = session.send(bytes.clone()); // ignore closed sessions
+            }
+        }
+    }
+}
+
+/// Keeps an authenticated connection to the peer open, sends the frames it receives over it and
+/// forwards the messages that the peer sends back to the inbound channel.
+async fn dial(
+    peer: SocketAddr,
+    mut frames: UnboundedReceiver<Arc<Vec<u8>>>,
+    inbound: UnboundedSender<(PeerID, Message)>,
+    shared: Arc<Shared>,
+) {
+    let set_state = |state| {
+        shared.connections.lock().unwrap().insert(peer, state);
+    };
+
+    let mut backoff = shared.config.min_backoff;
+    loop {
+        set_state(ConnectionState::Connecting);
+        let state = match TcpStream::connect(peer).await {
+            Ok(mut stream) => match authenticate(&mut stream, peer, Role::Initiator, &shared).await
+            {
+                Ok((issuer, session)) => {
+                    let peer_id = shared.peer_id(&issuer);
+                    set_state(ConnectionState::Connected);
+                    shared.set_connected(peer, Some(peer_id));
+                    info!(%issuer, "connected");
+                    backoff = shared.config.min_backoff;
+
+                    let state = tokio::select! {
+                        _ = exchange(stream, session, peer_id, &mut frames, &inbound) => {
+                            ConnectionState::Disconnected
+                        },
+                        _ = shared.admission.evicted(&issuer) => {
+                            Rejection::LeftCommittee.report(&peer, Some(&issuer));
+                            ConnectionState::Rejected
+                        },
+                    };
+                    shared.set_connected(peer, None);
+                    state
+                }
+                Err(Some(_)) => ConnectionState::Rejected,
+                Err(None) => ConnectionState::Disconnected,
+            },
+            Err(err) => {
+                debug!(%err, ?backoff, "failed to connect");
+                ConnectionState::Disconnected
+            }
+        };
+        set_state(state);
+
+        // drop the frames that are sent while the peer is unreachable
+        let retry_at = Instant::now() + backoff;
+        loop {
+            tokio::select! {
+                frame = frames.recv() => if frame.is_none() {
+                    return;
+                },
+                _ = sleep_until(retry_at) => break,
+            }
+        }
+        backoff = (backoff * 2).min(shared.config.max_backoff);
+    }
+}
+
+/// Accepts connections from peers and forwards the messages they send to the inbound channel.
+/// Returns once the endpoint is dropped.
+async fn accept(
+    listener: Arc<TcpListener>,
+    inbound: UnboundedSender<(PeerID, Message)>,
+    shared: Arc<Shared>,
+) {
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((stream, address)) => {
+                    debug!(%address, "accepted connection");
+                    tokio::spawn(
+                        receive(stream, address, inbound.clone(), shared.clone())
+                            .instrument(info_span!("peer", %address)),
+                    );
+                }
+                Err(err) => warn!(%err, "failed to accept connection"),
+            },
+            _ = inbound.closed() => return,
+        }
+    }
+}
+
+async fn receive(
+    mut stream: TcpStream,
+    address: SocketAddr,
+    inbound: UnboundedSender<(PeerID, Message)>,
+    shared: Arc<Shared>,
+) {
+    let authenticated = tokio::select! {
+        authenticated = authenticate(&mut stream, address, Role::Responder, &shared) => authenticated,
+        _ = inbound.closed() => return,
+    };
+    let Ok((issuer, session)) = authenticated else {
+        return;
+    };
+    let peer = shared.peer_id(&issuer);
+    debug!(%issuer, %peer, "peer authenticated");
+
+    let (frames, mut outgoing) = unbounded_channel();
+    shared.add_session(peer, frames.clone());
+
+    tokio::select! {
+        _ = exchange(stream, session, peer, &mut outgoing, &inbound) => {},
+        _ = inbound.closed() => {},
+        _ = shared.admission.evicted(&issuer) => {
+            Rejection::LeftCommittee.report(&address, Some(&issuer));
+        },
+    }
+    shared.remove_session(peer, &frames);
+}
+
+/// Sends the given frames to an authenticated peer and forwards the messages it sends to the
+/// inbound channel. Returns once the connection fails or the frames are closed.
+async fn exchange(
+    stream: TcpStream,
+    session: Session,
+    peer: PeerID,
+    frames: &mut UnboundedReceiver<Arc<Vec<u8>>>,
+    inbound: &UnboundedSender<(PeerID, Message)>,
+) {
+    // the halves of the connection are used concurrently, so the session is shared between them
+    let (mut reader, mut writer) = stream.into_split();
+    let session = Mutex::new(session);
+
+    let receive = async {
+        loop {
+            let frame = match read_frame(&mut reader, MAX_FRAME_LENGTH).await {
+                Ok(frame) => session.lock().unwrap().decrypt(&frame),
+                Err(err) => Err(err),
+            };
+            let frame = match frame {
+                Ok(frame) => frame,
+                Err(err) => {
+                    debug!(%err, "connection closed");
+                    return;
+                }
+            };
+
+            match Message::from_bytes(&frame) {
+                Ok(message) => {
+                    trace!("received message {:?}", message);
+                    if inbound.send((peer, message)).is_err() {
+                        return;
+                    }
+                }
+                Err(err) => {
+                    warn!(%err, "closing connection after receiving an invalid message");
+                    return;
+                }
+            }
+        }
+    };
+
+    let send = async {
+        while let Some(payload) = frames.recv().await {
+            let frame = session.lock().unwrap().encrypt(&payload);
+            let sent = match frame {
+                Ok(frame) => write_frame(&mut writer, &frame).await,
+                Err(err) => Err(err),
+            };
+            if let Err(err) = sent {
+                warn!(%err, "connection lost");
+                return;
+            }
+        }
+    };
+
+    tokio::select! {
+        _ = receive => {},
+        _ = send => {},
+    }
+}
+
+/// Runs the handshake on a new connection and checks that the peer is admitted. Failures are
+/// logged, and rejections are reported and returned.
+async fn authenticate(
+    stream: &mut TcpStream,
+    address: SocketAddr,
+    role: Role,
+    shared: &Shared,
+) -> Result<(IssuerID, Session), Option<Rejection>> {
+    let _ = stream.set_nodelay(true);
+
+    let rejection = match timeout(
+        shared.config.handshake_timeout,
+        handshake(stream, role, &shared.signing_key),
+    )
+    .await
+    {
+        Ok(Ok((issuer, session))) if shared.admission.admits(&issuer) => {
+            return Ok((issuer, session));
+        }
+        Ok(Ok((issuer, _))) => {
+            Rejection::NotAdmitted.report(&address, Some(&issuer));
+            Rejection::NotAdmitted
+        }
+        Ok(Err(HandshakeError::InvalidIdentity)) => {
+            Rejection::InvalidIdentity.report(&address, None);
+            Rejection::InvalidIdentity
+        }
+        Ok(Err(HandshakeError::Io(err))) => {
+            debug!(%err, "handshake failed");
+            return Err(None);
+        }
+        Err(_) => {
+            debug!("handshake timed out");
+            return Err(None);
+        }
+    };
+
+    Err(Some(rejection))
+}
+
+type CommitteeUpdate = (Option<Committee>, Option<Committee>);
@@ -0,0 +1,86 @@
+use std::io;
+
+use snow::TransportState;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use crate::frame::{read_frame, write_frame};
+
+/// The maximum length of a Noise message, including the authentication tag.
+pub(crate) const MAX_NOISE_MESSAGE: usize = 65535;
+
+/// The length of the authentication tag that is appended to every encrypted Noise message.
+const TAG_LENGTH: usize = 16;
+
+/// An encrypted session with an authenticated peer.
+///
+/// Payloads are split into chunks that fit into a single Noise message and the encrypted chunks
+/// are sent in one frame. All chunks but the last one have the maximum length, so the receiver
+/// can split the frame without additional length prefixes.
+pub(crate) struct Session {
+    transport: TransportState,
+    buffer: Vec<u8>,
+}
+
+impl Session {
+    pub(crate) fn new(transport: TransportState) -> Self {
+        Self {
+            transport,
+            buffer: vec![0; MAX_NOISE_MESSAGE],
+        }
+    }
+
+    pub(crate) async fn send<W: AsyncWrite + Unpin>(
+        &mut self,
+        writer: &mut W,
+        payload: &[u8],
+    ) -> io::Result<()> {
+        let frame = self.encrypt(payload)?;
+        write_frame(writer, &frame).await
+    }
+
+    /// Receives a payload whose encrypted frame is at most `max_length` bytes long.
+    pub(crate) async fn receive<R: AsyncRead + Unpin>(
+        &mut self,
+        reader: &mut R,
+        max_length: usize,
+    ) -> io::Result<Vec<u8>> {
+        let frame = read_frame(reader, max_length).await?;
+        self.decrypt(&frame)
+    }
+
+    /// Encrypts the payload into the content of a frame.
+    pub(crate) fn encrypt(&mut self, payload: &[u8]) -> io::Result<Vec<u8>> {
+        let chunk_length = MAX_NOISE_MESSAGE - TAG_LENGTH;
+        let chunk_count = payload.len().div_ceil(chunk_length).max(1);
+
+        let mut frame = Vec::with_capacity(payload.len() + chunk_count * TAG_LENGTH);
+        for i in 0..chunk_count {
+            let chunk = &payload[i * chunk_length..payload.len().min((i + 1) * chunk_length)];
+            let length = self
+                .transport
+                .write_message(chunk, &mut self.buffer)
+                .map_err(invalid_data)?;
+            frame.extend_from_slice(&self.buffer[..length]);
+        }
+
+        Ok(frame)
+    }
+
+    /// Decrypts the content of a frame produced by [`Session::encrypt`].
+    pub(crate) fn decrypt(&mut self, frame: &[u8]) -> io::Result<Vec<u8>> {
+        let mut payload = Vec::with_capacity(frame.len());
+        for chunk in frame.chunks(MAX_NOISE_MESSAGE) {
+            let length = self
+                .transport
+                .read_message(chunk, &mut self.buffer)
+                .map_err(invalid_data)?;
+            payload.extend_from_slice(&self.buffer[..length]);
+        }
+
+        Ok(payload)
+    }
+}
+
+pub(crate) fn invalid_data(err: snow::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
@@ -0,0 +1,178 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use common::{
+    bft::{Committee, Member},
+    crypto::SigningKey,
+    networking::Network as _,
+};
+use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+use tcp::{ConnectionState, REJECTED_PEERS_METRIC, Rejection, TcpConfig, TcpNetwork};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+/// Counts the rejected peers by reason.
+#[derive(Default)]
+struct RejectionRecorder {
+    counters: Mutex<HashMap<String, Arc<AtomicU64>>>,
+}
+
+impl RejectionRecorder {
+    fn get() -> &'static RejectionRecorder {
+        static RECORDER: OnceLock<&'static RejectionRecorder> = OnceLock::new();
+        RECORDER.get_or_init(|| {
+            let recorder = Box::leak(Box::default());
+            metrics::set_global_recorder(&*recorder).unwrap();
+            recorder
+        })
+    }
+
+    fn rejections(&self, reason: Rejection) -> u64 {
+        let counters = self.counters.lock().unwrap();
+        counters
+            .get(reason.as_str())
+            .map_or(0, |counter| counter.load(Ordering::Relaxed))
+    }
+}
+
+impl Recorder for &'static RejectionRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        if key.name() != REJECTED_PEERS_METRIC {
+            return Counter::noop();
+        }
+
+        let reason = key
+            .labels()
+            .find(|label| label.key() == "reason")
+            .map(|label| label.value().to_string())
+            .unwrap_or_default();
+        let mut counters = self.counters.lock().unwrap();
+        Counter::from_arc(counters.entry(reason).or_default().clone())
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+        Histogram::noop()
+    }
+}
+
+async fn eventually(mut condition: impl FnMut() -> bool) -> bool {
+    for _ in 0..500 {
+        if condition() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_non_committee_peers_are_rejected() {
+    let recorder = RejectionRecorder::get();
+    let validator_key = SigningKey::from([1; 32]);
+    let committee = Committee::from([Member::new(validator_key.issuer_id())]);
+
+    let intruder = TcpNetwork::bind(
+        SigningKey::from([42; 32]),
+        committee.clone(),
+        TcpConfig::default(),
+    )
+    .await
+    .unwrap();
+    let intruder_address = intruder.local_addr().unwrap();
+    let _intruder_endpoint = intruder.endpoint().await;
+
+    let before = recorder.rejections(Rejection::NotAdmitted);
+    let validator = TcpNetwork::bind(
+        validator_key,
+        committee,
+        TcpConfig {
+            peers: vec![intruder_address],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let _validator_endpoint = validator.endpoint().await;
+
+    assert!(
+        eventually(
+            || validator.connection_state(&intruder_address) == Some(ConnectionState::Rejected)
+        )
+        .await,
+        "intruder was not rejected"
+    );
+    assert!(recorder.rejections(Rejection::NotAdmitted) > before);
+}
+
+#[tokio::test]
+async fn test_reflected_identity_is_rejected() {
+    let recorder = RejectionRecorder::get();
+    let validator_key = SigningKey::from([1; 32]);
+    let validator = TcpNetwork::bind(
+        validator_key.clone(),
+        Committee::from([Member::new(validator_key.issuer_id())]),
+        TcpConfig::default(),
+    )
+    .await
+    .unwrap();
+    let _endpoint = validator.endpoint().await;
+    let before = recorder.rejections(Rejection::InvalidIdentity);
+
+    // run the noise handshake by hand and send the identity of the validator back to it
+    let mut stream = TcpStream::connect(validator.local_addr().unwrap())
+        .await
+        .unwrap();
+    let mut noise = snow::Builder::new("Noise_NN_25519_ChaChaPoly_BLAKE2s".parse().unwrap())
+        .build_initiator()
+        .unwrap();
+    let mut buffer = vec![0; 65535];
+    let length = noise.write_message(&[], &mut buffer).unwrap();
+    write_frame(&mut stream, &buffer[..length]).await;
+    noise
+        .read_message(&read_frame(&mut stream).await, &mut buffer)
+        .unwrap();
+
+    let mut transport = noise.into_transport_mode().unwrap();
+    let length = transport
+        .read_message(&read_frame(&mut stream).await, &mut buffer)
+        .unwrap();
+    let identity = buffer[..length].to_vec();
+    assert!(identity.starts_with(&validator_key.issuer_id()[..]));
+
+    let length = transport.write_message(&identity, &mut buffer).unwrap();
+    write_frame(&mut stream, &buffer[..length]).await;
+
+    assert!(
+        eventually(|| recorder.rejections(Rejection::InvalidIdentity) > before).await,
+        "reflected identity was not rejected"
+    );
+}
+
+async fn write_frame(stream: &mut TcpStream, payload: &[u8]) {
+    stream.write_u32(payload.len() as u32).await.unwrap();
+    stream.write_all(payload).await.unwrap();
+}
+
+async fn read_frame(stream: &mut TcpStream) -> Vec<u8> {
+    let mut frame = vec![0; stream.read_u32().await.unwrap() as usize];
+    stream.read_exact(&mut frame).await.unwrap();
+    frame
+}
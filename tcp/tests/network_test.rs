@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use common::{
+    bft::{Committee, Member},
+    blocks::{Block, NetworkBlock},
+    crypto::SigningKey,
+    ids::Id,
+    networking::{Endpoint, Message, Network as _, PeerID, Recipients},
+};
+use tcp::{TcpConfig, TcpNetwork};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+async fn first_peer(endpoint: &mut Endpoint) -> PeerID {
+    tokio::time::timeout(
+        Duration::from_secs(5),
+        endpoint.peers.wait_for(|peers| !peers.is_empty()),
+    )
+    .await
+    .expect("no peer connected")
+    .unwrap()[0]
+}
+
+#[tokio::test]
+async fn test_observers_are_sent_messages_over_their_connection() {
+    let validator_key = SigningKey::from([1; 32]);
+    let observer_key = SigningKey::from([9; 32]);
+    let committee = Committee::from([Member::new(validator_key.issuer_id())]);
+
+    // the validator does not dial the observer, which only connects to the validator
+    let validator = TcpNetwork::bind(
+        validator_key,
+        committee.clone(),
+        TcpConfig {
+            observers: vec![observer_key.issuer_id()],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let observer = TcpNetwork::bind(
+        observer_key,
+        committee,
+        TcpConfig {
+            peers: vec![validator.local_addr().unwrap()],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let mut validator_endpoint = validator.endpoint().await;
+    let mut observer_endpoint = observer.endpoint().await;
+    let observer_peer = first_peer(&mut validator_endpoint).await;
+
+    let block_id = Id::from([7; 32]);
+    validator_endpoint
+        .outbound
+        .send((
+            Recipients::Peers(vec![observer_peer]),
+            Message::BlockRequest(block_id.clone()),
+        ))
+        .unwrap();
+
+    let (_, message) =
+        tokio::time::timeout(Duration::from_secs(5), observer_endpoint.inbound.recv())
+            .await
+            .expect("observer received no message")
+            .unwrap();
+    assert!(matches!(message, Message::BlockRequest(id) if id == block_id));
+}
+
+#[tokio::test]
+async fn test_oversized_handshake_frames_are_refused() {
+    let network = TcpNetwork::bind(
+        SigningKey::from([1; 32]),
+        Committee::from([Member::new(SigningKey::from([1; 32]).issuer_id())]),
+        TcpConfig {
+            handshake_timeout: Duration::from_secs(30),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let _endpoint = network.endpoint().await;
+
+    // announce a frame far larger than any handshake message, which closes the connection long
+    // before the handshake times out
+    let mut stream = TcpStream::connect(network.local_addr().unwrap())
+        .await
+        .unwrap();
+    stream.write_all(&(1u32 << 20).to_be_bytes()).await.unwrap();
+
+    let mut buffer = [0; 1];
+    let read = tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buffer))
+        .await
+        .expect("connection was not closed");
+    assert!(matches!(read, Ok(0) | Err(_)));
+}
+
+#[tokio::test]
+async fn test_large_messages() {
+    let keys = [SigningKey::from([1; 32]), SigningKey::from([2; 32])];
+    let committee = Committee::from(keys.clone().map(|key| Member::new(key.issuer_id())));
+
+    let receiver = TcpNetwork::bind(keys[0].clone(), committee.clone(), TcpConfig::default())
+        .await
+        .unwrap();
+    let sender = TcpNetwork::bind(
+        keys[1].clone(),
+        committee,
+        TcpConfig {
+            peers: vec![receiver.local_addr().unwrap()],
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let mut receiver_endpoint = receiver.endpoint().await;
+    let mut sender_endpoint = sender.endpoint().await;
+    first_peer(&mut sender_endpoint).await;
+
+    // payloads that exceed a single noise message are split into chunks
+    let payload: Vec<u8> = (0..200_000).map(|i| i as u8).collect();
+    let block = Block::from(NetworkBlock::new(vec![Id::default()], 1, payload, &keys[1]));
+    sender_endpoint
+        .outbound
+        .send((Recipients::All, Message::Block(block.clone())))
+        .unwrap();
+
+    let (_, message) =
+        tokio::time::timeout(Duration::from_secs(5), receiver_endpoint.inbound.recv())
+            .await
+            .expect("no message received")
+            .unwrap();
+    assert!(matches!(message, Message::Block(received) if received.payload() == block.payload()));
+}